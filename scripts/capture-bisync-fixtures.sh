@@ -0,0 +1,80 @@
+#!/usr/bin/env bash
+# Capture real `rclone bisync` stderr for the parser tests in src/bisync_log.rs.
+#
+# Usage: scripts/capture-bisync-fixtures.sh v1.64.2 v1.68.2 ...
+#
+# For every release it downloads the linux-amd64 build, runs a few bisyncs between two local
+# temp directories the way `build_command` does, and writes the output to
+# tests/fixtures/bisync/<version>-<case>.log (text) and <version>-<case>-json.log
+# (`--use-json-log`).
+set -euo pipefail
+
+PROJECT_ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+OUT_DIR="${PROJECT_ROOT}/tests/fixtures/bisync"
+
+if [[ $# -eq 0 ]]; then
+    echo "usage: $0 <rclone version>..." >&2
+    exit 2
+fi
+
+WORK="$(mktemp -d)"
+trap 'rm -rf "${WORK}"' EXIT
+mkdir -p "${OUT_DIR}"
+
+fetch() {
+    local version="$1" dir="${WORK}/${1}"
+    mkdir -p "${dir}"
+    curl -fsSL -o "${dir}/rclone.zip" \
+        "https://downloads.rclone.org/${version}/rclone-${version}-linux-amd64.zip"
+    unzip -q -j "${dir}/rclone.zip" '*/rclone' -d "${dir}"
+    echo "${dir}/rclone"
+}
+
+# Runs one bisync and saves stderr; a non-zero exit is expected for some cases.
+bisync() {
+    local out="$1"
+    shift
+    "${RCLONE}" --config /dev/null bisync "${P1}" "${P2}" \
+        --stats 1s --stats-one-line --stats-log-level NOTICE "${LOG_ARGS[@]}" "$@" \
+        2>"${out}" || true
+}
+
+capture() {
+    local version="$1" suffix="$2"
+    local base="${WORK}/${version}${suffix}"
+    P1="${base}/path1"
+    P2="${base}/path2"
+    export XDG_CACHE_HOME="${base}/cache"
+    mkdir -p "${P1}" "${P2}"
+    echo one >"${P1}/a.txt"
+
+    # No prior listings: bisync asks for --resync.
+    bisync "${OUT_DIR}/${version}-resync-required${suffix}.log"
+    bisync "${WORK}/initial.log" --resync
+
+    # Changes on both sides, including one file edited on both.
+    echo two >"${P1}/b.txt"
+    echo path1 >"${P1}/a.txt"
+    sleep 1
+    echo path2 >"${P2}/a.txt"
+    bisync "${OUT_DIR}/${version}-changes${suffix}.log"
+
+    # Nothing changed.
+    bisync "${OUT_DIR}/${version}-no-changes${suffix}.log"
+
+    # Lock file left behind by a killed run.
+    local lock
+    lock="$(find "${XDG_CACHE_HOME}/rclone/bisync" -maxdepth 1 -name '*.lst' | head -n1)"
+    lock="${lock%.path?.lst}.lck"
+    echo 999999 >"${lock}"
+    bisync "${OUT_DIR}/${version}-prior-lock${suffix}.log"
+}
+
+for version in "$@"; do
+    RCLONE="$(fetch "${version}")"
+    LOG_ARGS=()
+    capture "${version}" ""
+    LOG_ARGS=(--use-json-log)
+    capture "${version}" "-json"
+    echo "Captured ${version} into ${OUT_DIR}"
+done
@@ -1,51 +1,166 @@
+use crate::bisync_log::ChangeKind;
+use crate::config_editor::{ConfigDraft, EditMessage};
+use crate::history::{self, HistoryRecord};
 use crate::job_config;
-use crate::status::{StatusStore, SyncState};
+use crate::remote_browser::{BrowseMessage, BrowseTarget, RemoteBrowser};
+use crate::runner::{QueueMode, RunOptions, RunOutcome, SyncProgress, Trigger};
+use crate::status::{DryRunPreview, StatusStore, SyncState};
 use crate::systemd::{SystemdUser, TimerStatus};
 
+use std::collections::BTreeSet;
 use std::fs;
-use std::io::Read;
 use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Context;
 use chrono::Utc;
+use cosmic::dialog::file_chooser;
 use cosmic::iced::widget::container;
+use cosmic::iced::widget::progress_bar;
 use cosmic::iced::widget::text::Wrapping;
 use cosmic::iced::widget::tooltip;
-use cosmic::iced::{Length, Limits, Subscription, window::Id};
+use cosmic::iced::{Color, Length, Limits, Subscription, window::Id};
 use cosmic::iced_winit::commands::popup::{destroy_popup, get_popup};
 use cosmic::prelude::*;
 use cosmic::widget;
 use cosmic::widget::settings;
 use cosmic::widget::text as ctext;
 
+/// Files listed under "Transferring" while a sync runs.
+const PROGRESS_MAX_FILES: usize = 4;
+const PREVIEW_MAX_FILES_PER_PAIR: usize = 50;
+/// Runs listed under "Recent runs".
+const RECENT_RUNS: usize = 5;
+/// How long "Sync now" via systemd shows as syncing before the unit takes the job lock.
+const DETACHED_START_GRACE: Duration = Duration::from_secs(15);
+/// A run changes several unit properties in a row; they are handled as one refresh.
+const UNIT_CHANGE_DEBOUNCE: Duration = Duration::from_millis(500);
+/// Job shown (and created) when the jobs dir is empty.
+const DEFAULT_JOB: &str = "default";
+
 #[derive(Default)]
 pub struct AppletModel {
     core: cosmic::Core,
     popup: Option<Id>,
+    /// One entry per `<job>.toml` in the jobs dir, sorted by name.
+    jobs: Vec<JobView>,
+    /// Job whose details are expanded in the popup.
+    expanded: Option<String>,
+    /// Settings view for one job; replaces the job list while open.
+    editor: Option<ConfigDraft>,
+    /// Create-job flow; replaces the job list while open.
+    new_job: Option<NewJobDraft>,
+    /// Remote folder picker for the editor or create-job flow; shown on top of them.
+    browser: Option<RemoteBrowser>,
+    /// Jobs whose units changed since the last debounced refresh.
+    changed_units: BTreeSet<String>,
+}
+
+/// Fields of the create-job flow.
+#[derive(Debug, Default)]
+struct NewJobDraft {
+    name: String,
+    local: String,
+    rclone_config_path: String,
+    /// Remotes from `rclone listremotes`, e.g. `gdrive:`.
+    remotes: Vec<String>,
+    remote_idx: Option<usize>,
+    /// Path on the chosen remote; empty syncs the remote's root.
+    remote_path: String,
+    initial_resync: bool,
+    loading_remotes: bool,
+    creating: bool,
+    error: Option<String>,
+}
+
+impl NewJobDraft {
+    fn remote(&self) -> Option<String> {
+        let remote = self.remotes.get(self.remote_idx?)?;
+        Some(format!(
+            "{remote}{}",
+            self.remote_path.trim().trim_start_matches('/')
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NewJobMessage {
+    Name(String),
+    Local(String),
+    BrowseLocal,
+    LocalPicked(Option<PathBuf>),
+    RcloneConfigPath(String),
+    LoadRemotes,
+    RemotesLoaded(Result<Vec<String>, String>),
+    Remote(usize),
+    RemotePath(String),
+    InitialResync(bool),
+    Create,
+    Created(Result<String, String>),
+}
+
+/// Everything the popup shows for one job.
+#[derive(Default)]
+struct JobView {
     job: String,
     state: SyncState,
     systemd_status: Option<TimerStatus>,
     systemd_error: Option<String>,
+    /// Why changing the job's units failed (after a settings change, install, repair or uninstall).
+    units_error: Option<String>,
     syncing: bool,
     manual_syncing: bool,
+    previewing: bool,
+    show_preview: bool,
     sync_started_at: Option<chrono::DateTime<chrono::Utc>>,
-    sync_log_tail: Vec<String>,
+    sync_trigger: Option<Trigger>,
+    /// Our "Sync now" is waiting for a run started elsewhere to finish.
+    sync_queued: bool,
+    /// When "Sync now" started a systemd unit that may not have taken the job lock yet.
+    detached_started: Option<std::time::Instant>,
+    sync_progress: Option<SyncProgress>,
+    recent_runs: Vec<HistoryRecord>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     TogglePopup,
     PopupClosed(Id),
-    ShowLogs,
     Refresh,
-    SyncNow,
-    SyncFinished(Result<SyncState, String>),
-    SyncLogTick,
-    SystemdInstall,
-    SystemdEnable,
-    SystemdDisable,
-    OpenConfigFile,
+    SyncProgressTick,
+    ToggleJob(String),
+    ShowLogs(String),
+    SyncNow(String),
+    PreviewChanges(String),
+    DismissPreview(String),
+    CancelSync(String),
+    SyncFinished(String, Result<SyncState, String>),
+    SystemdInstall(String),
+    SystemdEnable(String),
+    SystemdDisable(String),
+    SystemdRepair(String),
+    /// systemd reported a property change on one of our units.
+    UnitChanged(String),
+    /// No unit changed for `UNIT_CHANGE_DEBOUNCE`; refresh the jobs that did.
+    UnitChangesSettled,
+    /// Timer status of each job, queried off the UI thread.
+    SystemdStatusLoaded(Vec<(String, Result<TimerStatus, String>)>),
+    /// A change to a job's units finished: why it failed, if it did, and the new timer status.
+    UnitsUpdated(String, Option<String>, Result<TimerStatus, String>),
+    /// Jobs moved from per-job unit files to the templates, with why it failed if it did.
+    UnitsMigrated(Vec<(String, Option<String>)>),
+    SystemdUninstall(String),
+    OpenConfigFile(String),
+    EditConfig(String),
+    Edit(EditMessage),
+    CheckSchedule,
+    SaveConfig,
+    CloseEditor,
+    OpenNewJob,
+    NewJob(NewJobMessage),
+    CloseNewJob,
+    OpenBrowser(BrowseTarget),
+    Browse(BrowseMessage),
 }
 
 impl cosmic::Application for AppletModel {
@@ -67,26 +182,34 @@ impl cosmic::Application for AppletModel {
         core: cosmic::Core,
         _flags: Self::Flags,
     ) -> (Self, Task<cosmic::Action<Self::Message>>) {
-        let job = "default".to_string();
-        let state = StatusStore::load(&job)
-            .map(|s| s.state())
-            .unwrap_or_default();
-
         let mut app = AppletModel {
             core,
             popup: None,
-            job,
-            state,
-            systemd_status: None,
-            systemd_error: None,
-            syncing: false,
-            manual_syncing: false,
-            sync_started_at: None,
-            sync_log_tail: Vec::new(),
+            jobs: Vec::new(),
+            expanded: None,
+            editor: None,
+            new_job: None,
+            browser: None,
+            changed_units: BTreeSet::new(),
         };
-        app.refresh_systemd_summary();
-        app.refresh_syncing_summary();
-        (app, Task::none())
+        app.refresh_jobs();
+        // Jobs installed by older versions still have their own unit files.
+        let migrate = Task::perform(
+            async {
+                tokio::task::spawn_blocking(|| {
+                    SystemdUser::new()
+                        .and_then(|sd| sd.migrate_legacy_units())
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(job, res)| (job, res.err().map(|err| format!("{err:#}"))))
+                        .collect::<Vec<_>>()
+                })
+                .await
+                .unwrap_or_default()
+            },
+            |migrated| cosmic::action::app(Message::UnitsMigrated(migrated)),
+        );
+        (app, migrate)
     }
 
     fn on_close_requested(&self, id: Id) -> Option<Message> {
@@ -94,11 +217,16 @@ impl cosmic::Application for AppletModel {
     }
 
     fn view(&self) -> Element<'_, Self::Message> {
-        let icon = if self.syncing {
-            "content-loading-symbolic"
-        } else if self.state.last_error.is_some() {
+        // The panel icon shows the worst state across all jobs.
+        let jobs = &self.jobs;
+        let icon = if jobs
+            .iter()
+            .any(|j| !j.syncing && j.state.last_error.is_some())
+        {
             "dialog-error-symbolic"
-        } else if self.state.last_success.is_some() {
+        } else if jobs.iter().any(|j| j.syncing) {
+            "content-loading-symbolic"
+        } else if !jobs.is_empty() && jobs.iter().all(|j| j.state.last_success.is_some()) {
             "emblem-ok-symbolic"
         } else {
             "view-refresh-symbolic"
@@ -111,259 +239,39 @@ impl cosmic::Application for AppletModel {
     }
 
     fn view_window(&self, _id: Id) -> Element<'_, Self::Message> {
-        let state = &self.state;
-
-        let (status_label, status_color) = if self.syncing {
-            ("Syncing", cosmic::iced::Color::from_rgb(0.95, 0.75, 0.2))
-        } else if state.last_error.is_some() {
-            ("Error", cosmic::iced::Color::from_rgb(0.85, 0.25, 0.25))
-        } else if state.last_run.is_some() {
-            ("OK", cosmic::iced::Color::from_rgb(0.2, 0.7, 0.3))
-        } else {
-            ("Idle", cosmic::iced::Color::from_rgb(0.55, 0.55, 0.55))
-        };
-
-        let status_dot = widget::container(ctext::caption("●"))
-            .class(cosmic::theme::Container::custom(move |_theme| {
-                container::Style {
-                    text_color: Some(status_color),
-                    ..Default::default()
-                }
-            }))
-            .width(Length::Shrink);
-
-        let status_badge = widget::row()
-            .spacing(6)
-            .push(status_dot)
-            .push(ctext::caption(status_label).wrapping(Wrapping::Word));
-
         let refresh_button =
             widget::button::icon(cosmic::widget::icon::from_name("view-refresh-symbolic"))
                 .on_press(Message::Refresh);
-
-        let sync_now_button = widget::button::suggested(if self.syncing {
-            "Syncing…"
+        let new_job_button =
+            widget::button::icon(cosmic::widget::icon::from_name("list-add-symbolic"))
+                .on_press_maybe(self.new_job.is_none().then_some(Message::OpenNewJob));
+
+        let header = widget::row()
+            .spacing(10)
+            .push(ctext::title4("Rclone Sync Helper"))
+            .push(widget::container(widget::Space::with_width(Length::Fill)).width(Length::Fill))
+            .push(new_job_button)
+            .push(refresh_button);
+
+        let mut sections: Vec<Element<'_, Message>> = vec![header.into()];
+        if let Some(browser) = &self.browser {
+            sections.extend(browser_sections(browser));
+        } else if let Some(draft) = &self.new_job {
+            sections.extend(new_job_sections(draft));
+        } else if let Some(draft) = &self.editor {
+            sections.extend(editor_sections(draft));
+        } else if self.jobs.len() == 1 {
+            // A single job gets the full view directly, as before.
+            sections.extend(self.jobs[0].detail_sections());
         } else {
-            "Sync now"
-        })
-        .on_press_maybe((!self.syncing).then_some(Message::SyncNow));
-
-        let header = widget::column()
-            .spacing(2)
-            .push(
-                widget::row()
-                    .spacing(10)
-                    .push(ctext::title4("Rclone Sync Helper"))
-                    .push(
-                        widget::container(status_badge)
-                            .width(Length::Shrink)
-                            .padding([2, 8]),
-                    )
-                    .push(
-                        widget::container(widget::Space::with_width(Length::Fill))
-                            .width(Length::Fill),
-                    )
-                    .push(refresh_button),
-            )
-            .push(ctext::caption(format!("Job: {}", self.job)))
-            .push(sync_now_button);
-
-        let (status_section, logs_section): (Element<'_, Message>, Option<Element<'_, Message>>) =
-            if self.syncing {
-                let started = format_datetime(&self.sync_started_at);
-                let elapsed = self
-                    .sync_started_at
-                    .map(|t| (Utc::now() - t).num_seconds().max(0) as u64)
-                    .unwrap_or(0);
-                let logs = if self.sync_log_tail.is_empty() {
-                    "Starting…".to_string()
-                } else {
-                    self.sync_log_tail.join("\n")
-                };
-                let logs_widget = widget::scrollable::scrollable(
-                    widget::container(ctext::monotext(logs).size(12).wrapping(Wrapping::Word))
-                        .padding(8)
-                        .width(Length::Fill),
-                )
-                .height(Length::Fixed(200.0));
-
-                let show_logs_button =
-                    widget::button::standard("Show logs").on_press(Message::ShowLogs);
-
-                let status = settings::section()
-                    .title("Status")
-                    .add(settings::item(
-                        "Sync started",
-                        ctext::body(started).wrapping(Wrapping::Word),
-                    ))
-                    .add(settings::item(
-                        "Elapsed",
-                        ctext::body(format_duration(Duration::from_secs(elapsed)))
-                            .wrapping(Wrapping::Word),
-                    ));
-
-                // Create a full-width logs section
-                let logs_section = widget::column()
-                    .spacing(8)
-                    .width(Length::Fill)
-                    .push(logs_widget.width(Length::Fill))
-                    .push(show_logs_button);
-
-                (status.into(), Some(logs_section.into()))
-            } else if state.last_error.is_some() {
-                let last_run = format_datetime(&state.last_run);
-                let last_err = state
-                    .last_error
-                    .clone()
-                    .unwrap_or_else(|| "Unknown error".into());
-                let duration = state
-                    .last_duration_secs
-                    .map(|s| format_duration(Duration::from_secs(s)));
-                let show_logs_button =
-                    widget::button::standard("Show logs").on_press(Message::ShowLogs);
-                let status = settings::section()
-                    .title("Status")
-                    .add(settings::item(
-                        "Last run",
-                        ctext::body(last_run).wrapping(Wrapping::Word),
-                    ))
-                    .add_maybe(duration.map(|d| {
-                        settings::item("Duration", ctext::body(d).wrapping(Wrapping::Word))
-                    }))
-                    .add(settings::item(
-                        "Error",
-                        ctext::body(last_err).wrapping(Wrapping::Word),
-                    ))
-                    .add(settings::item("", show_logs_button));
-                (status.into(), None)
-            } else if state.last_success.is_some() {
-                let last_success_rel = format_relative_time(&state.last_success);
-                let last_success_exact = format_datetime(&state.last_success);
-                let duration = state
-                    .last_duration_secs
-                    .map(|s| format_duration(Duration::from_secs(s)));
-                let last_success_text = ctext::body(last_success_rel).wrapping(Wrapping::Word);
-                let last_success_widget = tooltip(
-                    last_success_text,
-                    ctext::body(last_success_exact),
-                    tooltip::Position::FollowCursor,
-                );
-                let show_logs_button =
-                    widget::button::standard("Show logs").on_press(Message::ShowLogs);
-                let mut section = settings::section()
-                    .title("Status")
-                    .add(settings::item("Last successful", last_success_widget));
-                if let Some(d) = duration {
-                    section = section.add(settings::item(
-                        "Duration",
-                        ctext::body(d).wrapping(Wrapping::Word),
-                    ));
-                }
-                // Only show Changes if we have a valid count
-                if let Some(count) = state.last_changed_count {
-                    section = section.add(settings::item(
-                        "Changes",
-                        ctext::body(count.to_string()).wrapping(Wrapping::Word),
-                    ));
+            for job in &self.jobs {
+                let expanded = self.expanded.as_deref() == Some(job.job.as_str());
+                sections.push(job.card(expanded));
+                if expanded {
+                    sections.extend(job.detail_sections());
                 }
-                let section = section.add(settings::item("", show_logs_button));
-                (section.into(), None)
-            } else {
-                let show_logs_button =
-                    widget::button::standard("Show logs").on_press(Message::ShowLogs);
-                let section = settings::section()
-                    .title("Status")
-                    .add(settings::item(
-                        "State",
-                        ctext::body("Idle").wrapping(Wrapping::Word),
-                    ))
-                    .add(settings::item("", show_logs_button));
-                (section.into(), None)
-            };
-
-        let remote = state
-            .remote_summary
-            .clone()
-            .unwrap_or_else(|| "Not yet detected".into());
-        let details_section = settings::section()
-            .title("Details")
-            .add(settings::item(
-                "Remote",
-                ctext::body(remote).wrapping(Wrapping::Word),
-            ))
-            .add(settings::item(
-                "Last run",
-                ctext::body(format_datetime(&state.last_run)).wrapping(Wrapping::Word),
-            ))
-            .add_maybe(self.state.last_log_file.as_ref().map(|p| {
-                settings::item(
-                    "Log file",
-                    ctext::caption(p.clone()).wrapping(Wrapping::Word),
-                )
-            }));
-
-        let (active, next, sd_err) = match (&self.systemd_status, &self.systemd_error) {
-            (Some(st), _) => (
-                st.enabled.to_string(),
-                st.next_elapse
-                    .clone()
-                    .unwrap_or_else(|| "Not scheduled".into()),
-                None,
-            ),
-            (None, Some(err)) => ("unknown".into(), "Not scheduled".into(), Some(err.clone())),
-            (None, None) => (
-                "unknown".into(),
-                "Not scheduled".into(),
-                Some("not checked yet".into()),
-            ),
-        };
-
-        let systemd_details = settings::section()
-            .title("Systemd timer")
-            .add(settings::item("Active", ctext::body(active)))
-            .add(settings::item(
-                "Next",
-                ctext::body(next).wrapping(Wrapping::Word),
-            ))
-            .add_maybe(
-                sd_err.map(|e| settings::item("Note", ctext::caption(e).wrapping(Wrapping::Word))),
-            );
-
-        let st = self.systemd_status.as_ref();
-        let show_install = st.map(|s| !s.installed).unwrap_or(true);
-        let show_enable = st.map(|s| s.installed && !s.enabled).unwrap_or(false);
-        let show_disable = st.map(|s| s.enabled).unwrap_or(false);
-
-        let systemd_actions =
-            widget::row()
-                .spacing(10)
-                .push_maybe(show_install.then_some(
-                    widget::button::suggested("Install").on_press(Message::SystemdInstall),
-                ))
-                .push_maybe(show_enable.then_some(
-                    widget::button::suggested("Enable").on_press(Message::SystemdEnable),
-                ))
-                .push_maybe(show_disable.then_some(
-                    widget::button::destructive("Disable").on_press(Message::SystemdDisable),
-                ));
-
-        let systemd_details = systemd_details.add(settings::item("Actions", systemd_actions));
-
-        let show_details =
-            state.last_error.is_some() || (!self.syncing && state.last_success.is_none());
-
-        let mut sections: Vec<Element<'_, Message>> = vec![header.into(), status_section.into()];
-        if let Some(logs) = logs_section {
-            sections.push(logs);
-        }
-        if show_details {
-            sections.push(details_section.into());
+            }
         }
-        sections.push(systemd_details.into());
-
-        let config_button =
-            widget::button::standard("Open config").on_press(Message::OpenConfigFile);
-        sections.push(config_button.into());
 
         let content = widget::scrollable::scrollable(settings::view_column(sections).padding(12))
             .height(Length::Shrink);
@@ -375,12 +283,14 @@ impl cosmic::Application for AppletModel {
         // Periodic refresh of cached state.
         let mut subs = vec![
             cosmic::iced::time::every(std::time::Duration::from_secs(30)).map(|_| Message::Refresh),
+            // Timer-triggered runs show up as soon as systemd reports them.
+            Subscription::run_with_id("systemd-unit-changes", unit_changes()),
         ];
 
-        if self.syncing {
+        if self.jobs.iter().any(|j| j.syncing) {
             subs.push(
                 cosmic::iced::time::every(std::time::Duration::from_secs(1))
-                    .map(|_| Message::SyncLogTick),
+                    .map(|_| Message::SyncProgressTick),
             );
         }
 
@@ -415,135 +325,1049 @@ impl cosmic::Application for AppletModel {
                     self.popup = None;
                 }
             }
-            Message::ShowLogs => {
-                // Try to open the log file from state first
-                let log_path = if let Some(log_file) = &self.state.last_log_file {
-                    let path = expand_home(log_file);
-                    if path.exists() {
-                        Some(path)
-                    } else {
-                        find_latest_log_file(&self.job).ok()
+            Message::Refresh => self.refresh_jobs(),
+            Message::SyncProgressTick => {
+                for job in self.jobs.iter_mut().filter(|j| j.syncing) {
+                    // Runs in other processes (timer, "Sync now" via systemd) are only
+                    // observed: reload the job once its lock is released.
+                    if !job.manual_syncing {
+                        job.refresh_syncing_summary();
+                        if !job.syncing {
+                            job.refresh();
+                            continue;
+                        }
                     }
+                    job.sync_progress = read_sync_progress(&job.job);
+                }
+            }
+            Message::ToggleJob(job) => {
+                if self.expanded.as_deref() == Some(job.as_str()) {
+                    self.expanded = None;
                 } else {
-                    find_latest_log_file(&self.job).ok()
+                    self.expanded = Some(job);
+                }
+            }
+            Message::ShowLogs(job) => {
+                let Some(view) = self.job(&job) else {
+                    return Task::none();
                 };
+                // Try to open the log file from state first
+                let log_path = view
+                    .state
+                    .last_log_file
+                    .as_deref()
+                    .map(job_config::expand_home)
+                    .filter(|p| p.exists())
+                    .or_else(|| find_latest_log_file(&job).ok());
 
                 if let Some(path) = log_path {
                     let _ = crate::open::open_log_file(&path);
                 }
             }
-            Message::OpenConfigFile => {
+            Message::OpenConfigFile(job) => {
                 // Ensure the file exists, then open it in the user's default editor.
-                let _ = job_config::load_or_create_job(&self.job);
-                if let Ok(path) = job_config::job_config_path(&self.job) {
+                let _ = job_config::load_or_create_job(&job);
+                if let Ok(path) = job_config::job_config_path(&job) {
                     let _ = crate::open::open_in_cosmic_edit(&path);
                 }
             }
-            Message::Refresh => {
-                if let Ok(store) = StatusStore::load(&self.job) {
-                    self.state = store.state();
+            Message::EditConfig(job) => match ConfigDraft::load(&job) {
+                Ok(draft) => self.editor = Some(draft),
+                Err(err) => {
+                    if let Some(view) = self.job_mut(&job) {
+                        view.state.last_error = Some(format!("Failed to load config: {err:#}"));
+                    }
+                }
+            },
+            Message::Edit(msg) => {
+                if let Some(draft) = self.editor.as_mut() {
+                    draft.update(msg);
+                }
+            }
+            Message::CheckSchedule => {
+                if let Some(draft) = self.editor.as_mut() {
+                    draft.check_schedule(5);
                 }
-                self.refresh_systemd_summary();
-                self.refresh_syncing_summary();
             }
-            Message::SyncNow => {
-                if self.syncing {
+            Message::SaveConfig => {
+                // A failed save keeps the editor open with the error shown.
+                let Some(draft) = self.editor.as_mut() else {
                     return Task::none();
+                };
+                let units_changed = draft.units_changed();
+                if draft.save().is_ok() {
+                    let job = draft.job().to_string();
+                    self.editor = None;
+                    if let Some(view) = self.job_mut(&job) {
+                        view.refresh_local();
+                    }
+                    // Apply a new schedule or resource limits to installed units right away.
+                    return units_task(job, move |sd, job| {
+                        if units_changed && sd.units_installed(job) {
+                            sd.install_units(job).context(
+                                "Saved, but the installed units still use the old settings",
+                            )?;
+                        }
+                        Ok(())
+                    });
                 }
-                self.syncing = true;
-                self.manual_syncing = true;
-                self.sync_started_at = Some(Utc::now());
-                self.sync_log_tail = tail_latest_sync_log_lines(&self.job).unwrap_or_default();
-                let job = self.job.clone();
-                return Task::perform(
+            }
+            Message::CloseEditor => self.editor = None,
+            Message::OpenNewJob => {
+                self.new_job = Some(NewJobDraft {
+                    initial_resync: true,
+                    ..Default::default()
+                });
+                return self.update_new_job(NewJobMessage::LoadRemotes);
+            }
+            Message::NewJob(msg) => return self.update_new_job(msg),
+            Message::CloseNewJob => self.new_job = None,
+            Message::OpenBrowser(target) => {
+                let (start, config_path) = match target {
+                    BrowseTarget::NewJob => self
+                        .new_job
+                        .as_ref()
+                        .map(|d| (d.remote().unwrap_or_default(), d.rclone_config_path.clone())),
+                    BrowseTarget::EditorRemote => self
+                        .editor
+                        .as_ref()
+                        .map(|d| (d.remote.clone(), d.rclone_config_path.clone())),
+                    // Pair remotes without a `remote:` are relative to the job's remote.
+                    BrowseTarget::EditorPair(idx) => self.editor.as_ref().map(|d| {
+                        let remote = d
+                            .pairs
+                            .get(idx)
+                            .map(|p| p.remote.clone())
+                            .filter(|r| r.contains(':'))
+                            .unwrap_or_else(|| d.remote.clone());
+                        (remote, d.rclone_config_path.clone())
+                    }),
+                }
+                .unwrap_or_default();
+                let browser = RemoteBrowser::new(target, &start, Some(config_path));
+                let config = browser.config_path.clone();
+                let list = browser
+                    .current()
+                    .map(|path| list_dirs_task(path, config.clone()));
+                self.browser = Some(browser);
+                let remotes = Task::perform(
                     async move {
-                        // rclone can run for a long time; use a blocking thread to keep the UI responsive.
                         tokio::task::spawn_blocking(move || {
-                            let cfg =
-                                job_config::load_or_create_job(&job).map_err(|e| format!("{e}"))?;
-                            let mut store = StatusStore::load(&job).map_err(|e| format!("{e}"))?;
-                            if let Err(err) = store.run_sync(&cfg) {
-                                store.set_last_error_and_persist(format!("Sync run failed: {err}"));
-                            }
-                            Ok::<SyncState, String>(store.state())
+                            crate::rclone::list_remotes(config.as_deref())
+                                .map_err(|e| e.to_string())
                         })
                         .await
-                        .map_err(|e| format!("Sync task failed: {e}"))?
+                        .map_err(|e| e.to_string())
+                        .and_then(|res| res)
                     },
-                    |res| cosmic::action::app(Message::SyncFinished(res)),
+                    |res| cosmic::action::app(Message::Browse(BrowseMessage::RemotesLoaded(res))),
                 );
+                return Task::batch([remotes].into_iter().chain(list));
             }
-            Message::SyncFinished(res) => {
-                self.manual_syncing = false;
-                match res {
-                    Ok(state) => self.state = state,
-                    Err(err) => self.state.last_error = Some(err),
-                }
-                // Notifications: errors -> critical; success with changes -> normal; no changes -> silent.
-                if let Some(code) = self.state.last_exit_code {
-                    if code != 0 {
-                        let body = self
-                            .state
-                            .last_error
-                            .clone()
-                            .unwrap_or_else(|| "Sync failed".into());
-                        let _ = crate::notify::notify("Rclone Sync Failed", &body, true);
-                    } else if let Some(changed) = self.state.last_changed_count {
-                        if changed > 0 {
-                            let body = format!("Synced {changed} item(s)");
-                            let _ = crate::notify::notify("Rclone Sync Completed", &body, false);
-                        }
+            Message::Browse(msg) => return self.update_browser(msg),
+            Message::SyncNow(job) => {
+                if let Some(view) = self.job_mut(&job) {
+                    let via_systemd = job_config::load_or_create_job(&job)
+                        .is_ok_and(|cfg| cfg.sync_now_via_systemd);
+                    if via_systemd && view.start_detached_sync() {
+                        return Task::none();
                     }
+                    return view.start_sync(RunOptions {
+                        trigger: Trigger::Applet,
+                        queue: QueueMode::Coalesce,
+                        ..Default::default()
+                    });
                 }
-                self.refresh_systemd_summary();
-                self.refresh_syncing_summary();
             }
-            Message::SyncLogTick => {
-                if self.syncing {
-                    self.sync_log_tail = tail_latest_sync_log_lines(&self.job).unwrap_or_default();
+            Message::PreviewChanges(job) => {
+                if let Some(view) = self.job_mut(&job).filter(|v| !v.syncing) {
+                    return view.start_sync(RunOptions {
+                        dry_run: true,
+                        trigger: Trigger::Applet,
+                        queue: QueueMode::Skip,
+                        ..Default::default()
+                    });
                 }
             }
-            Message::SystemdInstall => {
-                let _ = SystemdUser::new().and_then(|sd| sd.install_units(&self.job));
-                self.refresh_systemd_summary();
+            Message::DismissPreview(job) => {
+                if let Some(view) = self.job_mut(&job) {
+                    view.show_preview = false;
+                }
             }
-            Message::SystemdEnable => {
-                let _ = SystemdUser::new().and_then(|sd| sd.enable_timer(&self.job));
-                self.refresh_systemd_summary();
+            Message::CancelSync(job) => {
+                if self.job(&job).is_some_and(|v| v.syncing) {
+                    let _ = job_config::load_or_create_job(&job)
+                        .and_then(|cfg| crate::runner::cancel_job(&cfg));
+                }
             }
-            Message::SystemdDisable => {
-                let _ = SystemdUser::new().and_then(|sd| sd.disable_timer(&self.job));
-                self.refresh_systemd_summary();
+            Message::SyncFinished(job, res) => {
+                if let Some(view) = self.job_mut(&job) {
+                    view.sync_finished(res);
+                }
             }
-        }
-
-        Task::none()
-    }
-
-    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
-        Some(cosmic::applet::style())
-    }
-}
-
-impl AppletModel {
-    fn refresh_systemd_summary(&mut self) {
-        match SystemdUser::new().and_then(|sd| sd.status(&self.job)) {
-            Ok(st) => {
-                self.systemd_status = Some(st);
-                self.systemd_error = None;
+            Message::SystemdInstall(job) => {
+                return units_task(job, |sd, job| sd.install_units(job));
             }
-            Err(err) => {
-                self.systemd_status = None;
-                self.systemd_error = Some(err.to_string());
+            Message::SystemdEnable(job) => {
+                let _ = SystemdUser::new().and_then(|sd| sd.enable_timer(&job));
+                if let Some(view) = self.job_mut(&job) {
+                    view.refresh_systemd_summary();
+                }
             }
-        }
-    }
-
-    // Config editing moved out of the applet UI: we open the config file in the user's editor.
-
-    fn refresh_syncing_summary(&mut self) {
-        // If a manual sync is in flight, keep the syncing UI active regardless of lock timing.
-        if self.manual_syncing {
+            Message::SystemdDisable(job) => {
+                let _ = SystemdUser::new().and_then(|sd| sd.disable_timer(&job));
+                if let Some(view) = self.job_mut(&job) {
+                    view.refresh_systemd_summary();
+                }
+            }
+            Message::UnitChanged(unit) => {
+                let Some(job) = crate::systemd::job_from_unit(&unit) else {
+                    return Task::none();
+                };
+                // The first change of a burst starts the timer; later ones join it.
+                let first = self.changed_units.is_empty();
+                if self.job(&job).is_some() && self.changed_units.insert(job) && first {
+                    return Task::perform(tokio::time::sleep(UNIT_CHANGE_DEBOUNCE), |_| {
+                        cosmic::action::app(Message::UnitChangesSettled)
+                    });
+                }
+            }
+            Message::UnitChangesSettled => {
+                let jobs = std::mem::take(&mut self.changed_units);
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            jobs.into_iter()
+                                .map(|job| {
+                                    let st = load_systemd_status(&job);
+                                    (job, st)
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .await
+                        .unwrap_or_default()
+                    },
+                    |loaded| cosmic::action::app(Message::SystemdStatusLoaded(loaded)),
+                );
+            }
+            Message::UnitsUpdated(job, err, st) => {
+                if let Some(view) = self.job_mut(&job) {
+                    view.units_error = err;
+                    view.set_systemd_status(st);
+                }
+            }
+            Message::SystemdStatusLoaded(loaded) => {
+                for (job, st) in loaded {
+                    if let Some(view) = self.job_mut(&job) {
+                        view.set_systemd_status(st);
+                        view.refresh_local();
+                    }
+                }
+            }
+            Message::SystemdRepair(job) => {
+                return units_task(job, |sd, job| sd.repair_units(job).map(|_| ()));
+            }
+            Message::UnitsMigrated(migrated) => {
+                for (job, err) in migrated {
+                    if let Some(view) = self.job_mut(&job) {
+                        view.units_error =
+                            err.map(|err| format!("Moving to the template units failed: {err}"));
+                        view.refresh_systemd_summary();
+                    }
+                }
+            }
+            Message::SystemdUninstall(job) => {
+                return units_task(job, |sd, job| sd.remove_units(job));
+            }
+        }
+
+        Task::none()
+    }
+
+    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
+        Some(cosmic::applet::style())
+    }
+}
+
+impl AppletModel {
+    fn update_browser(&mut self, msg: BrowseMessage) -> Task<cosmic::Action<Message>> {
+        let Some(browser) = self.browser.as_mut() else {
+            return Task::none();
+        };
+        match msg {
+            BrowseMessage::RemotesLoaded(res) => {
+                let had_remote = browser.remote().is_some();
+                match res {
+                    Ok(remotes) => browser.set_remotes(remotes),
+                    Err(err) => browser.error = Some(err),
+                }
+                // Started without a remote: list the first one now that we have it.
+                if !had_remote {
+                    browser.loading = browser.remote().is_some();
+                    if let Some(path) = browser.current() {
+                        return list_dirs_task(path, browser.config_path.clone());
+                    }
+                }
+                return Task::none();
+            }
+            BrowseMessage::Listed(path, res) => {
+                // Ignore listings for folders we have navigated away from.
+                if browser.current().as_deref() == Some(path.as_str()) {
+                    browser.loading = false;
+                    match res {
+                        Ok(entries) => {
+                            browser.entries = entries;
+                            browser.error = None;
+                        }
+                        Err(err) => {
+                            browser.entries.clear();
+                            browser.error = Some(err);
+                        }
+                    }
+                }
+                return Task::none();
+            }
+            BrowseMessage::MeasureSize(name) => {
+                let Some(path) = browser.entry_path(&name) else {
+                    return Task::none();
+                };
+                browser.sizes.insert(path.clone(), None);
+                return folder_size_task(path, browser.config_path.clone());
+            }
+            BrowseMessage::SizeMeasured(path, res) => {
+                browser.sizes.insert(path, Some(res));
+                return Task::none();
+            }
+            BrowseMessage::Remote(idx) => browser.select_remote(idx),
+            BrowseMessage::Enter(name) => browser.enter(&name),
+            BrowseMessage::Up => browser.up(),
+            BrowseMessage::Cancel => {
+                self.browser = None;
+                return Task::none();
+            }
+            BrowseMessage::Select => {
+                let target = browser.target;
+                let remote = browser.remote().unwrap_or_default().to_string();
+                let path = browser.path.clone();
+                let selected = browser.current().unwrap_or_default();
+                self.browser = None;
+                match target {
+                    BrowseTarget::NewJob => {
+                        if let Some(draft) = self.new_job.as_mut() {
+                            if !draft.remotes.contains(&remote) {
+                                draft.remotes.push(remote.clone());
+                            }
+                            draft.remote_idx = draft.remotes.iter().position(|r| *r == remote);
+                            draft.remote_path = path;
+                        }
+                    }
+                    BrowseTarget::EditorRemote => {
+                        if let Some(draft) = self.editor.as_mut() {
+                            draft.update(EditMessage::Remote(selected));
+                        }
+                    }
+                    BrowseTarget::EditorPair(idx) => {
+                        if let Some(draft) = self.editor.as_mut() {
+                            draft.update(EditMessage::PairRemote(idx, selected));
+                        }
+                    }
+                }
+                return Task::none();
+            }
+        }
+        // Navigated: list the new folder.
+        browser.entries.clear();
+        browser.error = None;
+        browser.loading = true;
+        match browser.current() {
+            Some(path) => list_dirs_task(path, browser.config_path.clone()),
+            None => Task::none(),
+        }
+    }
+
+    fn update_new_job(&mut self, msg: NewJobMessage) -> Task<cosmic::Action<Message>> {
+        let Some(draft) = self.new_job.as_mut() else {
+            return Task::none();
+        };
+        draft.error = None;
+        match msg {
+            NewJobMessage::Name(v) => draft.name = v,
+            NewJobMessage::Local(v) => draft.local = v,
+            NewJobMessage::BrowseLocal => {
+                return Task::perform(
+                    async {
+                        file_chooser::open::Dialog::new()
+                            .title("Choose the local folder to sync")
+                            .open_folder()
+                            .await
+                            .ok()
+                            .and_then(|res| res.url().to_file_path().ok())
+                    },
+                    |path| cosmic::action::app(Message::NewJob(NewJobMessage::LocalPicked(path))),
+                );
+            }
+            NewJobMessage::LocalPicked(path) => {
+                if let Some(path) = path {
+                    draft.local = path.display().to_string();
+                }
+            }
+            NewJobMessage::RcloneConfigPath(v) => draft.rclone_config_path = v,
+            NewJobMessage::LoadRemotes => {
+                draft.loading_remotes = true;
+                let config_path = draft.rclone_config_path.clone();
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            crate::rclone::list_remotes(Some(config_path.as_str()))
+                                .map_err(|e| e.to_string())
+                        })
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|res| res)
+                    },
+                    |res| cosmic::action::app(Message::NewJob(NewJobMessage::RemotesLoaded(res))),
+                );
+            }
+            NewJobMessage::RemotesLoaded(res) => {
+                draft.loading_remotes = false;
+                match res {
+                    Ok(remotes) => {
+                        // Keep the selection if the same remote is still configured.
+                        let selected = draft.remote_idx.and_then(|i| draft.remotes.get(i).cloned());
+                        draft.remote_idx = selected
+                            .and_then(|r| remotes.iter().position(|x| *x == r))
+                            .or_else(|| (remotes.len() == 1).then_some(0));
+                        draft.remotes = remotes;
+                        if draft.remotes.is_empty() {
+                            draft.error = Some(
+                                "No rclone remotes configured; run `rclone config` first.".into(),
+                            );
+                        }
+                    }
+                    Err(err) => {
+                        draft.remotes.clear();
+                        draft.remote_idx = None;
+                        draft.error = Some(err);
+                    }
+                }
+            }
+            NewJobMessage::Remote(idx) => draft.remote_idx = Some(idx),
+            NewJobMessage::RemotePath(v) => draft.remote_path = v,
+            NewJobMessage::InitialResync(v) => draft.initial_resync = v,
+            NewJobMessage::Create => {
+                let Some(remote) = draft.remote() else {
+                    draft.error = Some("Choose a remote".into());
+                    return Task::none();
+                };
+                draft.creating = true;
+                let (name, local) = (draft.name.trim().to_string(), draft.local.clone());
+                let config_path = draft.rclone_config_path.clone();
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            let cfg = job_config::new_job(
+                                &name,
+                                &local,
+                                &remote,
+                                Some(config_path.as_str()),
+                            );
+                            job_config::validate_job_name(&name)
+                                .and_then(|_| crate::runner::validate_config(&cfg))
+                                .and_then(|_| crate::rclone::check_job_paths(&cfg))
+                                .and_then(|_| job_config::create_job(&cfg))
+                                .map(|_| name)
+                                .map_err(|e| e.to_string())
+                        })
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|res| res)
+                    },
+                    |res| cosmic::action::app(Message::NewJob(NewJobMessage::Created(res))),
+                );
+            }
+            NewJobMessage::Created(res) => {
+                draft.creating = false;
+                match res {
+                    Ok(job) => {
+                        let resync = draft.initial_resync;
+                        self.new_job = None;
+                        self.refresh_jobs();
+                        self.expanded = Some(job.clone());
+                        if resync && let Some(view) = self.job_mut(&job) {
+                            return view.start_sync(RunOptions {
+                                trigger: Trigger::Applet,
+                                resync: true,
+                                ..Default::default()
+                            });
+                        }
+                    }
+                    Err(err) => draft.error = Some(err),
+                }
+            }
+        }
+        Task::none()
+    }
+
+    fn job(&self, job: &str) -> Option<&JobView> {
+        self.jobs.iter().find(|j| j.job == job)
+    }
+
+    fn job_mut(&mut self, job: &str) -> Option<&mut JobView> {
+        self.jobs.iter_mut().find(|j| j.job == job)
+    }
+
+    /// Pick up jobs added to or removed from the jobs dir and refresh every job's status.
+    fn refresh_jobs(&mut self) {
+        let mut names = job_config::list_jobs().unwrap_or_default();
+        if names.is_empty() {
+            names.push(DEFAULT_JOB.to_string());
+        }
+
+        let mut previous = std::mem::take(&mut self.jobs);
+        for name in names {
+            let mut view = match previous.iter().position(|j| j.job == name) {
+                Some(idx) => previous.swap_remove(idx),
+                None => JobView {
+                    job: name,
+                    ..Default::default()
+                },
+            };
+            view.refresh();
+            self.jobs.push(view);
+        }
+        // Keep a job whose config vanished mid-sync until its run reports back.
+        self.jobs
+            .extend(previous.into_iter().filter(|j| j.manual_syncing));
+
+        if let Some(expanded) = &self.expanded
+            && !self.jobs.iter().any(|j| &j.job == expanded)
+        {
+            self.expanded = None;
+        }
+    }
+}
+
+impl JobView {
+    fn refresh(&mut self) {
+        self.refresh_systemd_summary();
+        self.refresh_local();
+    }
+
+    /// Reload everything but the timer status, which needs the user manager.
+    fn refresh_local(&mut self) {
+        if let Ok(store) = StatusStore::load(&self.job) {
+            self.state = store.state();
+        }
+        self.refresh_history();
+        self.refresh_syncing_summary();
+    }
+
+    fn status(&self) -> (&'static str, Color) {
+        let state = &self.state;
+        if self.syncing {
+            ("Syncing", Color::from_rgb(0.95, 0.75, 0.2))
+        } else if state.last_error.is_some() {
+            ("Error", Color::from_rgb(0.85, 0.25, 0.25))
+        } else if state.last_outcome == Some(RunOutcome::Cancelled) {
+            ("Cancelled", Color::from_rgb(0.55, 0.55, 0.55))
+        } else if state.last_run.is_some() {
+            ("OK", Color::from_rgb(0.2, 0.7, 0.3))
+        } else {
+            ("Idle", Color::from_rgb(0.55, 0.55, 0.55))
+        }
+    }
+
+    fn status_badge(&self) -> Element<'_, Message> {
+        let (status_label, status_color) = self.status();
+        let status_dot = widget::container(ctext::caption("●"))
+            .class(cosmic::theme::Container::custom(move |_theme| {
+                container::Style {
+                    text_color: Some(status_color),
+                    ..Default::default()
+                }
+            }))
+            .width(Length::Shrink);
+
+        widget::row()
+            .spacing(6)
+            .push(status_dot)
+            .push(ctext::caption(status_label).wrapping(Wrapping::Word))
+            .into()
+    }
+
+    fn sync_now_button(&self) -> Element<'_, Message> {
+        // While a sync started elsewhere (e.g. the timer) runs, "Sync now" queues one more run.
+        widget::button::suggested(if self.previewing {
+            "Previewing…"
+        } else if self.sync_queued {
+            "Sync queued…"
+        } else if self.manual_syncing {
+            "Syncing…"
+        } else if self.syncing {
+            "Queue another sync"
+        } else {
+            "Sync now"
+        })
+        .on_press_maybe((!self.manual_syncing).then(|| Message::SyncNow(self.job.clone())))
+        .into()
+    }
+
+    /// Compact summary shown for every job when several are configured.
+    fn card(&self, expanded: bool) -> Element<'_, Message> {
+        let last_success = if self.syncing {
+            "Syncing now".to_string()
+        } else {
+            format!(
+                "Last successful: {}",
+                format_relative_time(&self.state.last_success)
+            )
+        };
+        let toggle = widget::button::standard(if expanded { "Hide" } else { "Details" })
+            .on_press(Message::ToggleJob(self.job.clone()));
+
+        settings::section()
+            .title(self.job.as_str())
+            .add(settings::item_row(vec![
+                self.status_badge(),
+                ctext::caption(last_success)
+                    .wrapping(Wrapping::Word)
+                    .width(Length::Fill)
+                    .into(),
+                self.sync_now_button(),
+                toggle.into(),
+            ]))
+            .into()
+    }
+
+    fn detail_sections(&self) -> Vec<Element<'_, Message>> {
+        let state = &self.state;
+        let job = &self.job;
+
+        let preview_button = widget::button::standard("Preview changes")
+            .on_press_maybe((!self.syncing).then(|| Message::PreviewChanges(job.clone())));
+
+        let cancel_button = widget::button::destructive("Cancel")
+            .on_press_maybe(self.syncing.then(|| Message::CancelSync(job.clone())));
+
+        let header = widget::column()
+            .spacing(2)
+            .push(
+                widget::row()
+                    .spacing(10)
+                    .push(ctext::caption(format!("Job: {job}")))
+                    .push(
+                        widget::container(self.status_badge())
+                            .width(Length::Shrink)
+                            .padding([2, 8]),
+                    ),
+            )
+            .push(
+                widget::row()
+                    .spacing(10)
+                    .push(self.sync_now_button())
+                    .push(preview_button)
+                    .push(cancel_button),
+            );
+
+        let show_logs_button =
+            || widget::button::standard("Show logs").on_press(Message::ShowLogs(job.clone()));
+
+        let (status_section, logs_section): (Element<'_, Message>, Option<Element<'_, Message>>) =
+            if self.syncing {
+                let started = format_datetime(&self.sync_started_at);
+                let elapsed = self
+                    .sync_started_at
+                    .map(|t| (Utc::now() - t).num_seconds().max(0) as u64)
+                    .unwrap_or(0);
+
+                let status =
+                    settings::section()
+                        .title("Status")
+                        .add(settings::item(
+                            "Sync started",
+                            ctext::body(started).wrapping(Wrapping::Word),
+                        ))
+                        .add_maybe(self.sync_trigger.map(|trigger| {
+                            settings::item("Started by", ctext::body(trigger.label()))
+                        }))
+                        .add(settings::item(
+                            "Elapsed",
+                            ctext::body(format_duration(Duration::from_secs(elapsed)))
+                                .wrapping(Wrapping::Word),
+                        ));
+
+                let logs_section = widget::column()
+                    .spacing(8)
+                    .width(Length::Fill)
+                    .push(progress_section(self.sync_progress.as_ref()))
+                    .push(show_logs_button());
+
+                (status.into(), Some(logs_section.into()))
+            } else if state.last_error.is_some() {
+                let last_run = format_datetime(&state.last_run);
+                let last_err = state
+                    .last_error
+                    .clone()
+                    .unwrap_or_else(|| "Unknown error".into());
+                let duration = state
+                    .last_duration_secs
+                    .map(|s| format_duration(Duration::from_secs(s)));
+                let mut status = settings::section()
+                    .title("Status")
+                    .add(settings::item(
+                        "Last run",
+                        ctext::body(last_run).wrapping(Wrapping::Word),
+                    ))
+                    .add_maybe(duration.map(|d| {
+                        settings::item("Duration", ctext::body(d).wrapping(Wrapping::Word))
+                    }))
+                    .add(settings::item(
+                        "Error",
+                        ctext::body(last_err).wrapping(Wrapping::Word),
+                    ));
+                // With several pairs, show which ones failed and which went through.
+                if state.last_pairs.len() > 1 {
+                    for (idx, pair) in state.last_pairs.iter().enumerate() {
+                        let text = match &pair.error {
+                            Some(err) => format!(
+                                "✗ {} <-> {} (exit {}): {err}",
+                                pair.local, pair.remote, pair.exit_code
+                            ),
+                            None => format!("✓ {} <-> {}", pair.local, pair.remote),
+                        };
+                        status = status.add(settings::item(
+                            format!("Pair {}/{}", idx + 1, state.last_pairs.len()),
+                            ctext::caption(text).wrapping(Wrapping::Word),
+                        ));
+                    }
+                }
+                let status = status.add(settings::item("", show_logs_button()));
+                (status.into(), None)
+            } else if state.last_outcome == Some(RunOutcome::Cancelled) {
+                let section = settings::section()
+                    .title("Status")
+                    .add(settings::item(
+                        "Last run",
+                        ctext::body(format_datetime(&state.last_run)).wrapping(Wrapping::Word),
+                    ))
+                    .add(settings::item(
+                        "State",
+                        ctext::body("Cancelled").wrapping(Wrapping::Word),
+                    ))
+                    .add_maybe(state.last_success.map(|_| {
+                        settings::item(
+                            "Last successful",
+                            ctext::body(format_relative_time(&state.last_success))
+                                .wrapping(Wrapping::Word),
+                        )
+                    }))
+                    .add(settings::item("", show_logs_button()));
+                (section.into(), None)
+            } else if state.last_success.is_some() {
+                let last_success_rel = format_relative_time(&state.last_success);
+                let last_success_exact = format_datetime(&state.last_success);
+                let duration = state
+                    .last_duration_secs
+                    .map(|s| format_duration(Duration::from_secs(s)));
+                let last_success_text = ctext::body(last_success_rel).wrapping(Wrapping::Word);
+                let last_success_widget = tooltip(
+                    last_success_text,
+                    ctext::body(last_success_exact),
+                    tooltip::Position::FollowCursor,
+                );
+                let mut section = settings::section()
+                    .title("Status")
+                    .add(settings::item("Last successful", last_success_widget));
+                if let Some(d) = duration {
+                    section = section.add(settings::item(
+                        "Duration",
+                        ctext::body(d).wrapping(Wrapping::Word),
+                    ));
+                }
+                // Only show Changes if we have a valid count
+                if let Some(count) = state.last_changed_count {
+                    section = section.add(settings::item(
+                        "Changes",
+                        ctext::body(count.to_string()).wrapping(Wrapping::Word),
+                    ));
+                }
+                let section = section.add(settings::item("", show_logs_button()));
+                (section.into(), None)
+            } else {
+                let section = settings::section()
+                    .title("Status")
+                    .add(settings::item(
+                        "State",
+                        ctext::body("Idle").wrapping(Wrapping::Word),
+                    ))
+                    .add(settings::item("", show_logs_button()));
+                (section.into(), None)
+            };
+
+        let remote = state
+            .remote_summary
+            .clone()
+            .unwrap_or_else(|| "Not yet detected".into());
+        let details_section = settings::section()
+            .title("Details")
+            .add(settings::item(
+                "Remote",
+                ctext::body(remote).wrapping(Wrapping::Word),
+            ))
+            .add(settings::item(
+                "Last run",
+                ctext::body(format_datetime(&state.last_run)).wrapping(Wrapping::Word),
+            ))
+            .add_maybe(state.last_log_file.as_ref().map(|p| {
+                settings::item(
+                    "Log file",
+                    ctext::caption(p.clone()).wrapping(Wrapping::Word),
+                )
+            }));
+
+        let (active, next, sd_err) = match (&self.systemd_status, &self.systemd_error) {
+            (Some(st), _) => (
+                st.enabled.to_string(),
+                st.next_elapse
+                    .clone()
+                    .unwrap_or_else(|| "Not scheduled".into()),
+                st.manager_error
+                    .as_ref()
+                    .map(|e| format!("systemd user manager not reachable: {e}")),
+            ),
+            (None, Some(err)) => ("unknown".into(), "Not scheduled".into(), Some(err.clone())),
+            (None, None) => (
+                "unknown".into(),
+                "Not scheduled".into(),
+                Some("not checked yet".into()),
+            ),
+        };
+
+        let systemd_details = settings::section()
+            .title("Systemd timer")
+            .add(settings::item("Active", ctext::body(active)))
+            .add(settings::item(
+                "Next",
+                ctext::body(next).wrapping(Wrapping::Word),
+            ))
+            .add_maybe(
+                sd_err.map(|e| settings::item("Note", ctext::caption(e).wrapping(Wrapping::Word))),
+            );
+
+        let st = self.systemd_status.as_ref();
+        let systemd_details = systemd_details
+            .add_maybe(st.and_then(|s| s.last_trigger.clone()).map(|at| {
+                settings::item("Last triggered", ctext::body(at).wrapping(Wrapping::Word))
+            }))
+            .add_maybe(st.and_then(|s| s.last_result()).map(|result| {
+                settings::item("Last result", ctext::body(result).wrapping(Wrapping::Word))
+            }))
+            .add_maybe(st.filter(|s| !s.journal_tail.is_empty()).map(|s| {
+                settings::item_row(vec![
+                    ctext::caption(s.journal_tail.join("\n"))
+                        .wrapping(Wrapping::Word)
+                        .into(),
+                ])
+            }));
+        let show_install = st.map(|s| !s.installed).unwrap_or(true);
+        let show_enable = st.map(|s| s.installed && !s.enabled).unwrap_or(false);
+        let show_disable = st.map(|s| s.enabled).unwrap_or(false);
+        let show_uninstall = st.map(|s| s.installed).unwrap_or(false);
+        let outdated = st.filter(|s| s.installed && !s.outdated.is_empty());
+
+        let systemd_actions = widget::row()
+            .spacing(10)
+            .push_maybe(show_install.then(|| {
+                widget::button::suggested("Install").on_press(Message::SystemdInstall(job.clone()))
+            }))
+            .push_maybe(show_enable.then(|| {
+                widget::button::suggested("Enable").on_press(Message::SystemdEnable(job.clone()))
+            }))
+            .push_maybe(show_disable.then(|| {
+                widget::button::destructive("Disable")
+                    .on_press(Message::SystemdDisable(job.clone()))
+            }))
+            .push_maybe(show_uninstall.then(|| {
+                widget::button::standard("Uninstall")
+                    .on_press(Message::SystemdUninstall(job.clone()))
+            }));
+
+        let systemd_details = systemd_details
+            .add_maybe(outdated.map(|_| {
+                settings::item(
+                    "Units out of date",
+                    widget::button::suggested("Repair")
+                        .on_press(Message::SystemdRepair(job.clone())),
+                )
+            }))
+            .add_maybe(self.units_error.as_ref().map(|err| {
+                settings::item(
+                    "Units not updated",
+                    ctext::caption(err.clone()).wrapping(Wrapping::Word),
+                )
+            }))
+            .add(settings::item("Actions", systemd_actions));
+
+        let show_details =
+            state.last_error.is_some() || (!self.syncing && state.last_success.is_none());
+
+        let mut sections: Vec<Element<'_, Message>> = vec![header.into(), status_section];
+        if let Some(logs) = logs_section {
+            sections.push(logs);
+        }
+        if let Some(preview) = state
+            .last_preview
+            .as_ref()
+            .filter(|_| self.show_preview && !self.syncing)
+        {
+            sections.push(preview_section(job, preview));
+        }
+        if show_details {
+            sections.push(details_section.into());
+        }
+        if !self.recent_runs.is_empty() {
+            sections.push(recent_runs_section(&self.recent_runs));
+        }
+        sections.push(systemd_details.into());
+
+        let config_buttons = widget::row()
+            .spacing(10)
+            .push(
+                widget::button::standard("Edit settings")
+                    .on_press(Message::EditConfig(job.clone())),
+            )
+            .push(
+                widget::button::standard("Open config file")
+                    .on_press(Message::OpenConfigFile(job.clone())),
+            );
+        sections.push(config_buttons.into());
+        sections
+    }
+
+    fn start_sync(&mut self, opts: RunOptions) -> Task<cosmic::Action<Message>> {
+        if self.manual_syncing {
+            return Task::none();
+        }
+        // Keep showing the run we queue behind until ours takes over.
+        self.sync_queued = self.syncing;
+        if !self.sync_queued {
+            self.sync_started_at = Some(Utc::now());
+            self.sync_trigger = Some(opts.trigger);
+            self.sync_progress = None;
+        }
+        self.syncing = true;
+        self.manual_syncing = true;
+        self.previewing = opts.dry_run;
+        self.show_preview = false;
+        let job = self.job.clone();
+        Task::perform(
+            async move {
+                // rclone can run for a long time; use a blocking thread to keep the UI responsive.
+                let state = tokio::task::spawn_blocking({
+                    let job = job.clone();
+                    move || {
+                        let cfg =
+                            job_config::load_or_create_job(&job).map_err(|e| format!("{e}"))?;
+                        let mut store = StatusStore::load(&job).map_err(|e| format!("{e}"))?;
+                        if let Err(err) = store.run_sync(&cfg, &opts) {
+                            store.set_last_error_and_persist(format!("Sync run failed: {err}"));
+                        }
+                        Ok::<SyncState, String>(store.state())
+                    }
+                })
+                .await
+                .map_err(|e| format!("Sync task failed: {e}"))
+                .and_then(|res| res);
+                (job, state)
+            },
+            |(job, res)| cosmic::action::app(Message::SyncFinished(job, res)),
+        )
+    }
+
+    /// Start "Sync now" as a systemd unit and observe it through the job lock. Returns false if
+    /// systemd could not start it, so the caller can run it in-process instead.
+    fn start_detached_sync(&mut self) -> bool {
+        if self.manual_syncing {
+            return true;
+        }
+        match SystemdUser::new().and_then(|sd| sd.start_sync(&self.job, Trigger::Applet)) {
+            Ok(_) => {
+                if !self.syncing {
+                    self.sync_started_at = Some(Utc::now());
+                    self.sync_trigger = Some(Trigger::Applet);
+                    self.sync_progress = None;
+                }
+                self.syncing = true;
+                self.show_preview = false;
+                self.detached_started = Some(std::time::Instant::now());
+                true
+            }
+            Err(err) => {
+                tracing::warn!("Running {} in the applet instead: {err:#}", self.job);
+                false
+            }
+        }
+    }
+
+    fn sync_finished(&mut self, res: Result<SyncState, String>) {
+        self.manual_syncing = false;
+        self.sync_queued = false;
+        let previous_run = self.state.last_run;
+        match res {
+            Ok(state) => self.state = state,
+            Err(err) => self.state.last_error = Some(err),
+        }
+        if std::mem::take(&mut self.previewing) {
+            self.show_preview = true;
+            self.refresh_syncing_summary();
+            return;
+        }
+        // Notifications: errors -> critical; success with changes -> normal; no changes,
+        // cancelled or skipped (which leaves `last_run` alone) -> silent.
+        let silent = self.state.last_run == previous_run
+            || self.state.last_outcome == Some(RunOutcome::Cancelled);
+        if let Some(code) = self.state.last_exit_code.filter(|_| !silent) {
+            if code != 0 {
+                let body = self
+                    .state
+                    .last_error
+                    .clone()
+                    .unwrap_or_else(|| format!("Job {} failed", self.job));
+                let _ = crate::notify::notify("Rclone Sync Failed", &body, true);
+            } else if let Some(changed) = self.state.last_changed_count.filter(|c| *c > 0) {
+                let body = format!("Job {}: synced {changed} item(s)", self.job);
+                let _ = crate::notify::notify("Rclone Sync Completed", &body, false);
+            }
+        }
+        self.refresh_history();
+        self.refresh_systemd_summary();
+        self.refresh_syncing_summary();
+    }
+
+    fn refresh_history(&mut self) {
+        self.recent_runs = history::load(&self.job, RECENT_RUNS).unwrap_or_default();
+    }
+
+    fn refresh_systemd_summary(&mut self) {
+        self.set_systemd_status(load_systemd_status(&self.job));
+    }
+
+    fn set_systemd_status(&mut self, st: Result<TimerStatus, String>) {
+        match st {
+            Ok(st) => {
+                self.systemd_status = Some(st);
+                self.systemd_error = None;
+            }
+            Err(err) => {
+                self.systemd_status = None;
+                self.systemd_error = Some(err);
+            }
+        }
+    }
+
+    fn refresh_syncing_summary(&mut self) {
+        // If a manual sync is in flight, keep the syncing UI active regardless of lock timing.
+        if self.manual_syncing {
             self.syncing = true;
             if self.sync_started_at.is_none() {
                 self.sync_started_at = Some(Utc::now());
@@ -556,30 +1380,736 @@ impl AppletModel {
             Err(_) => {
                 self.syncing = false;
                 self.sync_started_at = None;
-                self.sync_log_tail.clear();
+                self.sync_progress = None;
                 return;
             }
         };
 
-        let lock_path = cfg
-            .lock_file
-            .as_deref()
-            .filter(|s| !s.trim().is_empty())
-            .unwrap_or("/tmp/rclone-sync.lock");
-
-        if let Some(info) = crate::runner::detect_running(lock_path) {
+        if let Some(info) = crate::runner::detect_running(&crate::runner::lock_file_path(&cfg)) {
             self.syncing = true;
+            self.detached_started = None;
             self.sync_started_at = info.started_at.or_else(|| Some(Utc::now()));
-            self.sync_log_tail = tail_latest_sync_log_lines(&self.job).unwrap_or_default();
+            self.sync_trigger = Some(info.trigger);
+            self.sync_progress = read_sync_progress(&self.job);
+        } else if self
+            .detached_started
+            .is_some_and(|at| at.elapsed() < DETACHED_START_GRACE)
+        {
+            // The unit we started hasn't taken the lock yet.
+            self.syncing = true;
         } else {
+            self.detached_started = None;
             self.syncing = false;
             self.sync_started_at = None;
-            self.sync_log_tail.clear();
+            self.sync_progress = None;
+        }
+    }
+}
+
+fn folder_size_task(path: String, config_path: Option<String>) -> Task<cosmic::Action<Message>> {
+    Task::perform(
+        async move {
+            let res = tokio::task::spawn_blocking({
+                let path = path.clone();
+                move || {
+                    crate::rclone::folder_size(&path, config_path.as_deref())
+                        .map_err(|e| e.to_string())
+                }
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|res| res);
+            (path, res)
+        },
+        |(path, res)| cosmic::action::app(Message::Browse(BrowseMessage::SizeMeasured(path, res))),
+    )
+}
+
+fn list_dirs_task(path: String, config_path: Option<String>) -> Task<cosmic::Action<Message>> {
+    Task::perform(
+        async move {
+            let res = tokio::task::spawn_blocking({
+                let path = path.clone();
+                move || {
+                    crate::rclone::list_dirs(&path, config_path.as_deref())
+                        .map_err(|e| e.to_string())
+                }
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|res| res);
+            (path, res)
+        },
+        |(path, res)| cosmic::action::app(Message::Browse(BrowseMessage::Listed(path, res))),
+    )
+}
+
+fn browser_sections(browser: &RemoteBrowser) -> Vec<Element<'_, Message>> {
+    let current = browser
+        .current()
+        .unwrap_or_else(|| "No remote selected".into());
+    let location = widget::row()
+        .spacing(8)
+        .push(
+            widget::button::icon(cosmic::widget::icon::from_name("go-up-symbolic")).on_press_maybe(
+                browser
+                    .can_go_up()
+                    .then_some(Message::Browse(BrowseMessage::Up)),
+            ),
+        )
+        .push(
+            ctext::body(current)
+                .wrapping(Wrapping::Word)
+                .width(Length::Fill),
+        );
+
+    let mut section = settings::section()
+        .title("Choose remote folder")
+        .add(settings::item(
+            "Remote",
+            widget::dropdown(&browser.remote_labels, browser.remote_idx, |idx| {
+                Message::Browse(BrowseMessage::Remote(idx))
+            }),
+        ))
+        .add(settings::item_row(vec![location.into()]));
+
+    if browser.loading {
+        section = section.add(settings::item_row(vec![ctext::caption("Loading…").into()]));
+    } else if let Some(err) = &browser.error {
+        section = section.add(settings::item_row(vec![
+            ctext::caption(err.clone()).wrapping(Wrapping::Word).into(),
+        ]));
+    } else if browser.entries.is_empty() {
+        section = section.add(settings::item_row(vec![
+            ctext::caption("No subfolders").into(),
+        ]));
+    }
+    for entry in browser.entries.iter().filter(|_| !browser.loading) {
+        let mut details = vec![format_datetime(&entry.mod_time)];
+        // Folders report no size in a listing; measuring one walks its whole tree.
+        let measured = browser
+            .entry_path(&entry.name)
+            .and_then(|path| browser.sizes.get(&path));
+        let mut can_measure = false;
+        match measured {
+            Some(Some(Ok(size))) => details.push(format!(
+                "{} in {} files",
+                format_bytes(size.bytes),
+                size.count
+            )),
+            Some(Some(Err(err))) => details.push(format!("Size unknown: {err}")),
+            Some(None) => details.push("Measuring…".into()),
+            None if entry.size >= 0 => details.push(format_bytes(entry.size as u64)),
+            None => can_measure = true,
+        }
+        let mut row: Vec<Element<'_, Message>> = vec![
+            widget::button::link(entry.name.clone())
+                .on_press(Message::Browse(BrowseMessage::Enter(entry.name.clone())))
+                .into(),
+            widget::container(widget::Space::with_width(Length::Fill))
+                .width(Length::Fill)
+                .into(),
+            ctext::caption(details.join(" · ")).into(),
+        ];
+        if can_measure {
+            row.push(
+                widget::button::link("Size")
+                    .on_press(Message::Browse(BrowseMessage::MeasureSize(
+                        entry.name.clone(),
+                    )))
+                    .into(),
+            );
+        }
+        section = section.add(settings::item_row(row));
+    }
+
+    let actions = widget::row()
+        .spacing(10)
+        .push(
+            widget::button::suggested("Select this folder").on_press_maybe(
+                browser
+                    .remote()
+                    .is_some()
+                    .then_some(Message::Browse(BrowseMessage::Select)),
+            ),
+        )
+        .push(widget::button::standard("Cancel").on_press(Message::Browse(BrowseMessage::Cancel)));
+
+    vec![section.into(), actions.into()]
+}
+
+fn new_job_sections(draft: &NewJobDraft) -> Vec<Element<'_, Message>> {
+    let on_input = |msg: fn(String) -> NewJobMessage| move |v| Message::NewJob(msg(v));
+
+    let remote_picker: Element<'_, Message> = if draft.loading_remotes {
+        ctext::body("Loading remotes…").into()
+    } else {
+        widget::dropdown(&draft.remotes, draft.remote_idx, |idx| {
+            Message::NewJob(NewJobMessage::Remote(idx))
+        })
+        .into()
+    };
+
+    let local_row = widget::row()
+        .spacing(8)
+        .push(
+            widget::text_input("/home/user/Documents", draft.local.clone())
+                .on_input(on_input(NewJobMessage::Local))
+                .width(Length::Fill),
+        )
+        .push(
+            widget::button::standard("Browse…")
+                .on_press(Message::NewJob(NewJobMessage::BrowseLocal)),
+        );
+
+    let section = settings::section()
+        .title("New job")
+        .add(settings::item(
+            "Name",
+            widget::text_input("documents", draft.name.clone())
+                .on_input(on_input(NewJobMessage::Name))
+                .width(Length::Fill),
+        ))
+        .add(settings::item("Local folder", local_row))
+        .add(settings::item(
+            "rclone config",
+            widget::row()
+                .spacing(8)
+                .push(
+                    widget::text_input("default", draft.rclone_config_path.clone())
+                        .on_input(on_input(NewJobMessage::RcloneConfigPath))
+                        .width(Length::Fill),
+                )
+                .push(widget::button::standard("Reload remotes").on_press_maybe(
+                    (!draft.loading_remotes).then_some(Message::NewJob(NewJobMessage::LoadRemotes)),
+                )),
+        ))
+        .add(settings::item("Remote", remote_picker))
+        .add(settings::item(
+            "Path on remote",
+            widget::row()
+                .spacing(8)
+                .push(
+                    widget::text_input("Documents", draft.remote_path.clone())
+                        .on_input(on_input(NewJobMessage::RemotePath))
+                        .width(Length::Fill),
+                )
+                .push(
+                    widget::button::standard("Browse…").on_press_maybe(
+                        draft
+                            .remote_idx
+                            .is_some()
+                            .then_some(Message::OpenBrowser(BrowseTarget::NewJob)),
+                    ),
+                ),
+        ))
+        .add(settings::item(
+            "Run initial --resync",
+            widget::toggler(draft.initial_resync)
+                .on_toggle(|v| Message::NewJob(NewJobMessage::InitialResync(v))),
+        ));
+
+    let actions = widget::column()
+        .spacing(8)
+        .push_maybe(
+            draft
+                .error
+                .as_ref()
+                .map(|err| ctext::body(err.clone()).wrapping(Wrapping::Word)),
+        )
+        .push(
+            widget::row()
+                .spacing(10)
+                .push(
+                    widget::button::suggested(if draft.creating {
+                        "Checking…"
+                    } else {
+                        "Create"
+                    })
+                    .on_press_maybe(
+                        (!draft.creating).then_some(Message::NewJob(NewJobMessage::Create)),
+                    ),
+                )
+                .push(widget::button::standard("Cancel").on_press(Message::CloseNewJob)),
+        );
+
+    vec![section.into(), actions.into()]
+}
+
+fn editor_sections(draft: &ConfigDraft) -> Vec<Element<'_, Message>> {
+    let input = |placeholder: &'static str, value: &str, msg: fn(String) -> EditMessage| {
+        widget::text_input(placeholder, value.to_string())
+            .on_input(move |v| Message::Edit(msg(v)))
+            .width(Length::Fill)
+    };
+    let toggle = |value: bool, msg: fn(bool) -> EditMessage| {
+        widget::toggler(value).on_toggle(move |v| Message::Edit(msg(v)))
+    };
+
+    let general = settings::section()
+        .title(format!("Settings: {}", draft.job()))
+        .add(settings::item(
+            "Local path",
+            input("/home/user/Sync", &draft.local_path, EditMessage::LocalPath),
+        ))
+        .add(settings::item(
+            "Remote",
+            widget::row()
+                .spacing(8)
+                .push(input("remote:path", &draft.remote, EditMessage::Remote))
+                .push(
+                    widget::button::standard("Browse…")
+                        .on_press(Message::OpenBrowser(BrowseTarget::EditorRemote)),
+                ),
+        ));
+
+    let mut pairs = settings::section().title("Pairs");
+    if draft.pairs.is_empty() {
+        pairs = pairs.add(settings::item_row(vec![
+            ctext::caption("No pairs: local path and remote are synced as one pair.")
+                .wrapping(Wrapping::Word)
+                .into(),
+        ]));
+    }
+    let last = draft.pairs.len().saturating_sub(1);
+    for (idx, pair) in draft.pairs.iter().enumerate() {
+        let icon_button = |icon: &'static str, msg: Option<EditMessage>| {
+            widget::button::icon(cosmic::widget::icon::from_name(icon))
+                .on_press_maybe(msg.map(Message::Edit))
+        };
+        pairs = pairs.add(settings::item_row(vec![
+            widget::text_input("local", pair.local.clone())
+                .on_input(move |v| Message::Edit(EditMessage::PairLocal(idx, v)))
+                .width(Length::Fill)
+                .into(),
+            widget::text_input("remote", pair.remote.clone())
+                .on_input(move |v| Message::Edit(EditMessage::PairRemote(idx, v)))
+                .width(Length::Fill)
+                .into(),
+            icon_button(
+                "go-up-symbolic",
+                (idx > 0).then_some(EditMessage::MovePairUp(idx)),
+            )
+            .into(),
+            icon_button(
+                "go-down-symbolic",
+                (idx < last).then_some(EditMessage::MovePairDown(idx)),
+            )
+            .into(),
+            widget::button::icon(cosmic::widget::icon::from_name("folder-open-symbolic"))
+                .on_press(Message::OpenBrowser(BrowseTarget::EditorPair(idx)))
+                .into(),
+            icon_button("edit-delete-symbolic", Some(EditMessage::RemovePair(idx))).into(),
+        ]));
+    }
+    let pairs = pairs.add(settings::item(
+        "",
+        widget::button::standard("Add pair").on_press(Message::Edit(EditMessage::AddPair)),
+    ));
+
+    let advanced = settings::section()
+        .title("Advanced")
+        .add(settings::item(
+            "Extra rclone args",
+            input("--fast-list", &draft.extra_args, EditMessage::ExtraArgs),
+        ))
+        .add(settings::item(
+            "rclone config",
+            input(
+                "~/.config/rclone/rclone.conf",
+                &draft.rclone_config_path,
+                EditMessage::RcloneConfigPath,
+            ),
+        ))
+        .add(settings::item(
+            "Lock file",
+            input("default", &draft.lock_file, EditMessage::LockFile),
+        ))
+        .add(settings::item(
+            "Log directory",
+            input(
+                "~/logs/rclone-sync/<job>",
+                &draft.log_dir,
+                EditMessage::LogDir,
+            ),
+        ))
+        .add(settings::item(
+            "Resync when bisync asks for it",
+            toggle(draft.auto_resync, EditMessage::AutoResync),
+        ))
+        .add(settings::item(
+            "Clean stale bisync locks",
+            toggle(draft.clean_bisync_locks, EditMessage::CleanBisyncLocks),
+        ))
+        .add(settings::item(
+            "Low CPU/IO priority",
+            toggle(draft.use_nice_ionice, EditMessage::UseNiceIonice),
+        ))
+        .add(settings::item(
+            "Run Sync now through systemd",
+            toggle(draft.sync_now_via_systemd, EditMessage::SyncNowViaSystemd),
+        ));
+
+    let mut schedule = settings::section()
+        .title("Schedule")
+        .add(settings::item(
+            "Calendar (OnCalendar)",
+            input("hourly", &draft.on_calendar, EditMessage::OnCalendar),
+        ))
+        .add(settings::item(
+            "Or every (interval)",
+            input("e.g. 30min", &draft.interval, EditMessage::Interval),
+        ))
+        .add(settings::item(
+            "Random delay",
+            input(
+                "e.g. 5min",
+                &draft.randomized_delay,
+                EditMessage::RandomizedDelay,
+            ),
+        ))
+        .add(settings::item(
+            "Also after boot",
+            input("e.g. 10min", &draft.on_boot, EditMessage::OnBoot),
+        ))
+        .add(settings::item(
+            "Also after login",
+            input("e.g. 2min", &draft.on_startup, EditMessage::OnStartup),
+        ))
+        .add(settings::item(
+            "Catch up missed runs",
+            toggle(draft.persistent, EditMessage::Persistent),
+        ));
+    schedule = match &draft.schedule_preview {
+        Some(Ok(next)) if next.is_empty() => schedule.add(settings::item(
+            "Runs",
+            ctext::body(draft.schedule().describe()),
+        )),
+        Some(Ok(next)) => schedule.add(settings::item(
+            "Next runs",
+            ctext::body(next.join("\n")).wrapping(Wrapping::Word),
+        )),
+        Some(Err(err)) => schedule.add(settings::item(
+            "Invalid",
+            ctext::caption(err.clone()).wrapping(Wrapping::Word),
+        )),
+        None => schedule,
+    };
+    let schedule = schedule.add(settings::item(
+        "",
+        widget::button::standard("Check schedule").on_press(Message::CheckSchedule),
+    ));
+
+    let resources = settings::section()
+        .title("Timer runs")
+        .add(settings::item(
+            "Nice (-20 to 19)",
+            input("unset", &draft.nice, EditMessage::Nice),
+        ))
+        .add(settings::item(
+            "CPU weight (1-10000)",
+            input("100", &draft.cpu_weight, EditMessage::CpuWeight),
+        ))
+        .add(settings::item(
+            "IO weight (1-10000)",
+            input("100", &draft.io_weight, EditMessage::IoWeight),
+        ))
+        .add(settings::item(
+            "Memory limit",
+            input("e.g. 1G", &draft.memory_max, EditMessage::MemoryMax),
+        ))
+        .add(settings::item(
+            "Stop after",
+            input("e.g. 2h", &draft.timeout, EditMessage::Timeout),
+        ))
+        .add(settings::item(
+            "Wait for the network",
+            toggle(draft.wait_for_network, EditMessage::WaitForNetwork),
+        ))
+        .add(settings::item(
+            "Only on AC power",
+            toggle(draft.require_ac_power, EditMessage::RequireAcPower),
+        ));
+
+    let actions = widget::column()
+        .spacing(8)
+        .push_maybe(
+            draft
+                .error
+                .as_ref()
+                .map(|err| ctext::body(err.clone()).wrapping(Wrapping::Word)),
+        )
+        .push(
+            widget::row()
+                .spacing(10)
+                .push(widget::button::suggested("Save").on_press(Message::SaveConfig))
+                .push(widget::button::standard("Cancel").on_press(Message::CloseEditor)),
+        );
+
+    vec![
+        general.into(),
+        pairs.into(),
+        schedule.into(),
+        resources.into(),
+        advanced.into(),
+        actions.into(),
+    ]
+}
+
+/// Units whose properties changed, from the user manager's PropertiesChanged signals. Empty if
+/// D-Bus is unavailable; the periodic refresh still covers that case.
+fn unit_changes() -> impl futures_util::Stream<Item = Message> {
+    use futures_util::StreamExt;
+
+    futures_util::stream::once(crate::systemd_dbus::watch_unit_changes())
+        .filter_map(|res| async move { res.ok() })
+        .flatten()
+        .map(Message::UnitChanged)
+}
+
+/// Run `op` on the job's units off the UI thread and report back with `Message::UnitsUpdated`.
+fn units_task(
+    job: String,
+    op: impl FnOnce(&SystemdUser, &str) -> anyhow::Result<()> + Send + 'static,
+) -> Task<cosmic::Action<Message>> {
+    Task::perform(
+        async move {
+            let (err, st) = tokio::task::spawn_blocking({
+                let job = job.clone();
+                move || {
+                    let err = SystemdUser::new()
+                        .and_then(|sd| op(&sd, &job))
+                        .err()
+                        .map(|err| format!("{err:#}"));
+                    (err, load_systemd_status(&job))
+                }
+            })
+            .await
+            .unwrap_or_else(|err| (Some(err.to_string()), Err(err.to_string())));
+            (job, err, st)
+        },
+        |(job, err, st)| cosmic::action::app(Message::UnitsUpdated(job, err, st)),
+    )
+}
+
+/// Blocks on D-Bus when the user manager is slow to answer.
+fn load_systemd_status(job: &str) -> Result<TimerStatus, String> {
+    SystemdUser::new()
+        .and_then(|sd| sd.status(job))
+        .map_err(|err| err.to_string())
+}
+
+fn read_sync_progress(job: &str) -> Option<SyncProgress> {
+    // Don't recreate the config of a job deleted while its sync runs.
+    let cfg = crate::jobs::load_existing(job).ok()?;
+    crate::runner::read_progress(&crate::runner::lock_file_path(&cfg))
+}
+
+fn progress_section(progress: Option<&SyncProgress>) -> Element<'_, Message> {
+    let mut section = settings::section().title("Progress");
+    let Some(progress) = progress else {
+        return section
+            .add(settings::item("Pair", ctext::body("Starting…")))
+            .into();
+    };
+
+    section = section.add(settings::item(
+        "Pair",
+        ctext::body(format!(
+            "{} of {}: {} <-> {}",
+            progress.pair, progress.pair_count, progress.local, progress.remote
+        ))
+        .wrapping(Wrapping::Word),
+    ));
+
+    let Some(stats) = progress.stats.as_ref() else {
+        return section
+            .add(settings::item(
+                "Transferred",
+                ctext::body("Comparing listings…"),
+            ))
+            .into();
+    };
+
+    let percent = stats.percent();
+    section = section
+        .add(settings::item_row(vec![
+            progress_bar(0.0..=100.0, percent.unwrap_or(0) as f32).into(),
+        ]))
+        .add(settings::item(
+            "Transferred",
+            ctext::body(format!(
+                "{} / {}{}",
+                format_bytes(stats.bytes),
+                format_bytes(stats.total_bytes),
+                percent.map(|p| format!(" ({p}%)")).unwrap_or_default()
+            ))
+            .wrapping(Wrapping::Word),
+        ))
+        .add(settings::item(
+            "Files",
+            ctext::body(format!(
+                "{} / {} transferred, {} checked",
+                stats.transfers, stats.total_transfers, stats.checks
+            ))
+            .wrapping(Wrapping::Word),
+        ))
+        .add(settings::item(
+            "Speed",
+            ctext::body(format!("{}/s", format_bytes(stats.speed as u64))),
+        ))
+        .add(settings::item(
+            "ETA",
+            ctext::body(
+                stats
+                    .eta
+                    .map(|secs| format_duration(Duration::from_secs_f64(secs.max(0.0))))
+                    .unwrap_or_else(|| "—".into()),
+            ),
+        ));
+
+    if !stats.transferring.is_empty() {
+        let mut lines: Vec<String> = stats
+            .transferring
+            .iter()
+            .take(PROGRESS_MAX_FILES)
+            .map(|f| format!("{:>3}%  {}", f.percentage, f.name))
+            .collect();
+        if stats.transferring.len() > PROGRESS_MAX_FILES {
+            lines.push(format!(
+                "… and {} more",
+                stats.transferring.len() - PROGRESS_MAX_FILES
+            ));
+        }
+        section = section.add(settings::item_row(vec![
+            widget::column()
+                .spacing(4)
+                .push(ctext::caption("Transferring"))
+                .push(
+                    ctext::monotext(lines.join("\n"))
+                        .size(12)
+                        .wrapping(Wrapping::Word),
+                )
+                .into(),
+        ]));
+    }
+    section.into()
+}
+
+fn recent_runs_section(runs: &[HistoryRecord]) -> Element<'_, Message> {
+    let mut section = settings::section().title("Recent runs");
+    for run in runs {
+        let mut summary = format!("{} · {}", run.outcome.label(), run.trigger.label());
+        if let Some(secs) = run.duration_secs {
+            summary.push_str(&format!(
+                " · {}",
+                format_duration(Duration::from_secs(secs))
+            ));
+        }
+        if let Some(changed) = run.changed_count.filter(|c| *c > 0) {
+            summary.push_str(&format!(" · {changed} changed"));
         }
+        let details = widget::column()
+            .spacing(2)
+            .push(ctext::body(summary).wrapping(Wrapping::Word))
+            .push_maybe(
+                run.error
+                    .as_ref()
+                    .map(|err| ctext::caption(err.clone()).wrapping(Wrapping::Word)),
+            );
+        section = section.add(settings::item(
+            format_datetime(&Some(run.started_at)),
+            details,
+        ));
     }
+    section.into()
 }
 
-// Pair parsing/formatting moved to the config file (opened in user's editor).
+fn preview_section<'a>(job: &str, preview: &'a DryRunPreview) -> Element<'a, Message> {
+    let mut section = settings::section()
+        .title("Preview (dry run)")
+        .add(settings::item(
+            "Checked",
+            ctext::body(format_relative_time(&Some(preview.timestamp))).wrapping(Wrapping::Word),
+        ));
+    if preview.exit_code != 0 {
+        section = section.add(settings::item(
+            "Note",
+            ctext::caption(format!(
+                "rclone exited with code {}; see the log for details",
+                preview.exit_code
+            ))
+            .wrapping(Wrapping::Word),
+        ));
+    }
+
+    for pair in &preview.pairs {
+        let counts = [("Path1", &pair.path1), ("Path2", &pair.path2)]
+            .iter()
+            .filter_map(|(side, s)| {
+                s.map(|s| {
+                    format!(
+                        "{side}: {} new, {} newer, {} older, {} deleted",
+                        s.new, s.newer, s.older, s.deleted
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut lines: Vec<String> = pair
+            .changes
+            .iter()
+            .take(PREVIEW_MAX_FILES_PER_PAIR)
+            .map(|c| {
+                let kind = match c.kind {
+                    ChangeKind::New => "new",
+                    ChangeKind::Newer => "newer",
+                    ChangeKind::Older => "older",
+                    ChangeKind::Modified => "modified",
+                    ChangeKind::Deleted => "deleted",
+                };
+                format!("{:?} {kind:<8} {}", c.side, c.path)
+            })
+            .collect();
+        if pair.changes.len() > PREVIEW_MAX_FILES_PER_PAIR {
+            lines.push(format!(
+                "… and {} more",
+                pair.changes.len() - PREVIEW_MAX_FILES_PER_PAIR
+            ));
+        }
+        if lines.is_empty() {
+            lines.push("No changes".into());
+        }
+
+        let body = widget::column()
+            .spacing(4)
+            .push_maybe(
+                (!counts.is_empty()).then(|| ctext::caption(counts).wrapping(Wrapping::Word)),
+            )
+            .push(
+                ctext::monotext(lines.join("\n"))
+                    .size(12)
+                    .wrapping(Wrapping::Word),
+            );
+        section = section.add(settings::item_row(vec![
+            widget::column()
+                .spacing(4)
+                .push(
+                    ctext::body(format!("{} <-> {}", pair.local, pair.remote))
+                        .wrapping(Wrapping::Word),
+                )
+                .push(body)
+                .into(),
+        ]));
+    }
+
+    section
+        .add(settings::item(
+            "",
+            widget::button::standard("Dismiss").on_press(Message::DismissPreview(job.to_string())),
+        ))
+        .into()
+}
 
 fn format_datetime(value: &Option<chrono::DateTime<chrono::Utc>>) -> String {
     value
@@ -632,60 +2162,35 @@ fn format_duration(d: Duration) -> String {
     }
 }
 
-fn find_latest_log_file(job: &str) -> anyhow::Result<PathBuf> {
-    let cfg = job_config::load_or_create_job(job)?;
-    let dir: PathBuf = if let Some(dir) = cfg.log_dir.as_deref().filter(|s| !s.trim().is_empty()) {
-        expand_home(dir)
+/// Binary units, matching how rclone reports sizes (`1.204 MiB`).
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
     } else {
-        let home = std::env::var_os("HOME").ok_or_else(|| anyhow::anyhow!("HOME is not set"))?;
-        PathBuf::from(home).join("logs/rclone-sync")
-    };
-
-    let mut newest: Option<(std::time::SystemTime, PathBuf)> = None;
-    let entries =
-        fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
-    for ent in entries {
-        let ent = match ent {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-        let path = ent.path();
-        let name_ok = path
-            .file_name()
-            .and_then(|os| os.to_str())
-            .map(|s| s.starts_with("sync_") && s.ends_with(".log"))
-            .unwrap_or(false);
-        if !name_ok {
-            continue;
-        }
-        let meta = match fs::metadata(&path) {
-            Ok(m) => m,
-            Err(_) => continue,
-        };
-        let mtime = meta.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-        if newest.as_ref().map(|(t, _)| mtime > *t).unwrap_or(true) {
-            newest = Some((mtime, path));
-        }
+        format!("{value:.1} {}", UNITS[unit])
     }
-
-    newest
-        .map(|(_, path)| path)
-        .ok_or_else(|| anyhow::anyhow!("No log files found"))
 }
 
-fn tail_latest_sync_log_lines(job: &str) -> anyhow::Result<Vec<String>> {
+fn find_latest_log_file(job: &str) -> anyhow::Result<PathBuf> {
     let cfg = job_config::load_or_create_job(job)?;
-    let dir: PathBuf = if let Some(dir) = cfg.log_dir.as_deref().filter(|s| !s.trim().is_empty()) {
-        expand_home(dir)
-    } else {
-        let home = std::env::var_os("HOME").ok_or_else(|| anyhow::anyhow!("HOME is not set"))?;
-        PathBuf::from(home).join("logs/rclone-sync")
-    };
+    let dir = job_config::log_dir(&cfg)?;
+
+    // Logs written before the per-job log directories are in the old shared one.
+    newest_log_file(&dir)
+        .or_else(|| job_config::legacy_log_dir(&cfg).and_then(|dir| newest_log_file(&dir)))
+        .ok_or_else(|| anyhow::anyhow!("No log files found in {}", dir.display()))
+}
 
+fn newest_log_file(dir: &std::path::Path) -> Option<PathBuf> {
     let mut newest: Option<(std::time::SystemTime, PathBuf)> = None;
-    let entries =
-        fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
-    for ent in entries {
+    for ent in fs::read_dir(dir).ok()? {
         let ent = match ent {
             Ok(e) => e,
             Err(_) => continue,
@@ -709,35 +2214,5 @@ fn tail_latest_sync_log_lines(job: &str) -> anyhow::Result<Vec<String>> {
         }
     }
 
-    let Some((_, path)) = newest else {
-        return Ok(Vec::new());
-    };
-
-    Ok(read_full_log_file(&path)?)
-}
-
-fn read_full_log_file(path: &PathBuf) -> anyhow::Result<Vec<String>> {
-    let mut f = fs::File::open(path)?;
-    let mut buf = String::new();
-    f.read_to_string(&mut buf)?;
-
-    let mut out = Vec::new();
-    for line in buf.lines() {
-        out.push(line.to_string());
-    }
-    Ok(out)
-}
-
-fn expand_home(path: &str) -> PathBuf {
-    if let Some(rest) = path.strip_prefix("~/") {
-        if let Some(home) = std::env::var_os("HOME") {
-            return PathBuf::from(home).join(rest);
-        }
-    }
-    if let Some(rest) = path.strip_prefix("$HOME/") {
-        if let Some(home) = std::env::var_os("HOME") {
-            return PathBuf::from(home).join(rest);
-        }
-    }
-    PathBuf::from(path)
+    newest.map(|(_, path)| path)
 }
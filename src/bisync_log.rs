@@ -0,0 +1,852 @@
+//! Typed parsing of rclone bisync output.
+//!
+//! rclone writes human-oriented log lines (`2026/01/11 22:25:26 INFO  : ...`) interleaved with
+//! multi-line stats blocks. Everything that needs to understand a run (recovery decisions, change
+//! counts, error summaries) goes through [`parse`] instead of searching the raw text itself.
+//!
+//! Runs started by this helper use `--use-json-log`, so each line is a JSON object instead. Those
+//! are recognised line by line: the message goes through the same classification as text output,
+//! and periodic stats arrive as [`RcloneStats`] rather than as text blocks.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl LogLevel {
+    fn from_label(label: &str) -> Option<Self> {
+        Some(match label.to_ascii_uppercase().as_str() {
+            "DEBUG" => LogLevel::Debug,
+            "INFO" => LogLevel::Info,
+            "NOTICE" => LogLevel::Notice,
+            "WARNING" => LogLevel::Warning,
+            "ERROR" => LogLevel::Error,
+            "CRITICAL" | "ALERT" | "EMERGENCY" => LogLevel::Critical,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BisyncEvent {
+    /// One stats block (`Transferred:`, `Checks:`, `Deleted:`, ...).
+    Stats(StatsBlock),
+    /// The `stats` object of a JSON log line.
+    JsonStats(RcloneStats),
+    /// `PathN: X changes: ...`, including the `(Modified: ...)` breakdown of newer versions.
+    Changes {
+        side: PathSide,
+        summary: ChangeSummary,
+    },
+    /// `No changes found`.
+    NoChanges,
+    /// A per-file decision made while comparing listings.
+    FileChange(PlannedChange),
+    /// A file that was new or changed on both sides.
+    Conflict { path: String },
+    /// `prior lock file found: <path>`.
+    PriorLockFile { path: String },
+    /// bisync refuses to continue until it is run with `--resync`.
+    ResyncRequired,
+    /// A line logged at ERROR or above (also emitted alongside the more specific events).
+    Error { level: LogLevel, message: String },
+    /// `Bisync successful`.
+    Success,
+    /// Anything not classified above.
+    Log {
+        level: Option<LogLevel>,
+        message: String,
+    },
+}
+
+/// Counters from bisync's `PathN: X changes: ...` summary (and the `(Modified: ...)` breakdown
+/// printed by newer rclone versions).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeSummary {
+    pub total: u32,
+    pub new: u32,
+    pub modified: u32,
+    pub newer: u32,
+    pub older: u32,
+    pub deleted: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedChange {
+    pub side: PathSide,
+    pub kind: ChangeKind,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathSide {
+    Path1,
+    Path2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    New,
+    Newer,
+    Older,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsBlock {
+    pub bytes: Option<ByteProgress>,
+    pub transfers: Option<Counter>,
+    pub checks: Option<Counter>,
+    pub copied: Option<Counter>,
+    pub deleted_files: Option<u32>,
+    pub deleted_dirs: Option<u32>,
+    pub errors: Option<u32>,
+    pub elapsed: Option<String>,
+}
+
+/// `done / total, percent%` counters such as `Transferred: 52 / 262, 20%`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counter {
+    pub done: u32,
+    pub total: u32,
+    pub percent: Option<u32>,
+}
+
+/// The byte line of a stats block, kept as rclone formats it (`72.278 MiB`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteProgress {
+    pub done: String,
+    pub total: String,
+    pub percent: Option<u32>,
+    pub speed: Option<String>,
+    pub eta: Option<String>,
+}
+
+/// rclone's accounting stats as written to JSON log lines (`--use-json-log --stats ...`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RcloneStats {
+    pub bytes: u64,
+    pub total_bytes: u64,
+    pub transfers: u64,
+    pub total_transfers: u64,
+    pub checks: u64,
+    pub total_checks: u64,
+    pub deletes: u64,
+    pub deleted_dirs: u64,
+    pub renames: u64,
+    pub errors: u64,
+    /// Seconds; `null` while rclone cannot estimate it yet.
+    pub eta: Option<f64>,
+    /// Bytes per second.
+    pub speed: f64,
+    pub elapsed_time: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub transferring: Vec<TransferringFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl RcloneStats {
+    /// Overall byte progress, if rclone knows the total yet.
+    pub fn percent(&self) -> Option<u32> {
+        (self.total_bytes > 0)
+            .then(|| (self.bytes.saturating_mul(100) / self.total_bytes).min(100) as u32)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TransferringFile {
+    pub name: String,
+    pub size: i64,
+    pub bytes: i64,
+    pub percentage: u32,
+    pub speed: f64,
+    pub eta: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonLogLine {
+    #[serde(default)]
+    time: Option<String>,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    msg: String,
+    /// Set for messages about a single file; text logs print it as `<object>: <msg>`.
+    #[serde(default)]
+    object: Option<String>,
+    #[serde(default)]
+    stats: Option<RcloneStats>,
+}
+
+impl JsonLogLine {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+
+    fn level(&self) -> Option<LogLevel> {
+        self.level.as_deref().and_then(LogLevel::from_label)
+    }
+
+    fn message(&self) -> String {
+        match self.object.as_deref().filter(|o| !o.is_empty()) {
+            Some(object) => format!("{object}: {}", self.msg),
+            None => self.msg.clone(),
+        }
+    }
+}
+
+/// The stats object of a single JSON log line, for following progress while rclone runs.
+pub fn json_stats(line: &str) -> Option<RcloneStats> {
+    JsonLogLine::parse(line)?.stats
+}
+
+/// Turn a JSON log line back into rclone's text layout (`2026/01/11 22:25:26 INFO  : ...`) for
+/// the run log. Anything that is not a JSON log line is returned unchanged.
+pub fn render_line(line: &str) -> String {
+    let Some(entry) = JsonLogLine::parse(line) else {
+        return line.to_string();
+    };
+    let time = entry
+        .time
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| format!("{} ", t.format("%Y/%m/%d %H:%M:%S")))
+        .unwrap_or_default();
+    let level = entry
+        .level
+        .as_deref()
+        .map(|l| format!("{:<6}: ", l.to_ascii_uppercase()))
+        .unwrap_or_default();
+    format!("{time}{level}{}", entry.message().trim_end())
+}
+
+pub fn parse_output(stdout: &str, stderr: &str) -> Vec<BisyncEvent> {
+    let mut events = parse(stdout);
+    events.extend(parse(stderr));
+    events
+}
+
+pub fn parse(text: &str) -> Vec<BisyncEvent> {
+    let mut events = Vec::new();
+    let mut stats: Option<StatsBlock> = None;
+
+    for raw in text.lines() {
+        if let Some(entry) = JsonLogLine::parse(raw) {
+            events.extend(stats.take().map(BisyncEvent::Stats));
+            if let Some(json_stats) = entry.stats {
+                events.push(BisyncEvent::JsonStats(json_stats));
+                continue;
+            }
+            let level = entry.level();
+            for message in entry.message().lines().map(str::trim) {
+                if !message.is_empty() {
+                    classify(level, message, &mut events);
+                }
+            }
+            continue;
+        }
+
+        let (level, message) = split_log_prefix(raw);
+
+        if level.is_none() {
+            // Lines of the "Transferring:" section belong to the current block.
+            if stats.is_some() && message.trim_start().starts_with('*') {
+                continue;
+            }
+            if let Some((label, value)) = split_stats_line(message) {
+                let block = stats.get_or_insert_with(StatsBlock::default);
+                if !apply_stats_line(block, label, value) {
+                    // The same label again starts a new block.
+                    events.extend(stats.take().map(BisyncEvent::Stats));
+                    let mut block = StatsBlock::default();
+                    apply_stats_line(&mut block, label, value);
+                    stats = Some(block);
+                }
+                continue;
+            }
+        }
+        events.extend(stats.take().map(BisyncEvent::Stats));
+
+        let message = message.trim();
+        if message.is_empty() {
+            continue;
+        }
+        classify(level, message, &mut events);
+    }
+    events.extend(stats.take().map(BisyncEvent::Stats));
+    events
+}
+
+/// Split `2026/01/11 22:25:26 INFO  : message` into its level and message. Lines without the
+/// usual prefix (stats continuation lines, `Failed to bisync: ...`) come back unchanged.
+fn split_log_prefix(line: &str) -> (Option<LogLevel>, &str) {
+    let rest = strip_timestamp(line);
+    if let Some((label, message)) = rest.split_once(':')
+        && let Some(level) = LogLevel::from_label(label.trim())
+    {
+        return (Some(level), message.strip_prefix(' ').unwrap_or(message));
+    }
+    (None, rest)
+}
+
+fn strip_timestamp(line: &str) -> &str {
+    // `YYYY/MM/DD HH:MM:SS` optionally followed by fractional seconds.
+    let bytes = line.as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        bytes
+            .get(range)
+            .is_some_and(|b| b.iter().all(u8::is_ascii_digit))
+    };
+    let looks_like_timestamp = bytes.len() >= 19
+        && digits(0..4)
+        && bytes[4] == b'/'
+        && digits(5..7)
+        && bytes[7] == b'/'
+        && digits(8..10)
+        && bytes[10] == b' '
+        && digits(11..13)
+        && bytes[13] == b':'
+        && digits(14..16)
+        && bytes[16] == b':'
+        && digits(17..19);
+    if !looks_like_timestamp {
+        return line;
+    }
+    let rest = &line[19..];
+    let rest = rest
+        .strip_prefix('.')
+        .map(|r| r.trim_start_matches(|c: char| c.is_ascii_digit()))
+        .unwrap_or(rest);
+    rest.trim_start()
+}
+
+const STATS_LABELS: &[&str] = &[
+    "Transferred",
+    "Checks",
+    "Copied",
+    "Deleted",
+    "Renamed",
+    "Errors",
+    "Elapsed time",
+    "Transferring",
+    "Server Side Copies",
+    "Server Side Moves",
+];
+
+fn split_stats_line(message: &str) -> Option<(&str, &str)> {
+    let (label, value) = message.trim().split_once(':')?;
+    STATS_LABELS.contains(&label).then(|| (label, value.trim()))
+}
+
+/// Store one stats line in `block`. Returns `false` if the field was already set.
+fn apply_stats_line(block: &mut StatsBlock, label: &str, value: &str) -> bool {
+    fn set<T>(slot: &mut Option<T>, value: Option<T>) -> bool {
+        if slot.is_some() {
+            return false;
+        }
+        *slot = value;
+        true
+    }
+
+    match label {
+        "Transferred" => {
+            if let Some(counter) = parse_counter(value) {
+                set(&mut block.transfers, Some(counter))
+            } else {
+                set(&mut block.bytes, parse_byte_progress(value))
+            }
+        }
+        "Checks" => set(&mut block.checks, parse_counter(value)),
+        "Copied" => set(&mut block.copied, parse_counter(value)),
+        "Deleted" => {
+            // `1 (files), 0 (dirs)` (newer versions append `, 1.2 KiB (freed)`).
+            let mut nums = value
+                .split(',')
+                .filter_map(|p| p.split_whitespace().next()?.parse::<u32>().ok());
+            let files = nums.next();
+            let dirs = nums.next();
+            set(&mut block.deleted_files, files) && set(&mut block.deleted_dirs, dirs)
+        }
+        "Errors" => set(
+            &mut block.errors,
+            value.split_whitespace().next().and_then(|n| n.parse().ok()),
+        ),
+        "Elapsed time" => set(&mut block.elapsed, Some(value.to_string())),
+        _ => true,
+    }
+}
+
+fn parse_counter(value: &str) -> Option<Counter> {
+    let (counts, rest) = value.split_once(',')?;
+    let (done, total) = counts.split_once(" / ")?;
+    Some(Counter {
+        done: done.trim().parse().ok()?,
+        total: total.trim().parse().ok()?,
+        percent: parse_percent(rest),
+    })
+}
+
+fn parse_byte_progress(value: &str) -> Option<ByteProgress> {
+    let mut parts = value.split(',').map(str::trim);
+    let (done, total) = parts.next()?.split_once(" / ")?;
+    let percent = parts.next().and_then(parse_percent);
+    let speed = parts.next().map(str::to_string);
+    let eta = parts
+        .next()
+        .and_then(|p| p.strip_prefix("ETA"))
+        .map(|p| p.trim().to_string())
+        .filter(|p| p != "-");
+    Some(ByteProgress {
+        done: done.trim().to_string(),
+        total: total.trim().to_string(),
+        percent,
+        speed,
+        eta,
+    })
+}
+
+fn parse_percent(value: &str) -> Option<u32> {
+    value.trim().strip_suffix('%')?.trim().parse().ok()
+}
+
+fn classify(level: Option<LogLevel>, message: &str, events: &mut Vec<BisyncEvent>) {
+    let lower = message.to_lowercase();
+    let before = events.len();
+
+    if message == "Bisync successful" {
+        events.push(BisyncEvent::Success);
+    } else if message == "No changes found" {
+        events.push(BisyncEvent::NoChanges);
+    } else if let Some(rest) = message.strip_prefix("(Modified:") {
+        // Belongs to the `PathN: ... changes` line right before it.
+        if let Some(BisyncEvent::Changes { summary, .. }) = events
+            .iter_mut()
+            .rev()
+            .find(|e| matches!(e, BisyncEvent::Changes { .. }))
+        {
+            apply_counters(summary, rest.trim_end_matches(')'));
+        }
+        return;
+    } else if let Some((side, summary)) = parse_change_summary(message) {
+        events.push(BisyncEvent::Changes { side, summary });
+    } else if let Some(change) = parse_file_change(message) {
+        events.push(BisyncEvent::FileChange(change));
+    } else if message.contains("New or changed in both paths") {
+        let path = message
+            .rsplit_once(" - ")
+            .map(|(_, p)| p.trim().to_string())
+            .unwrap_or_default();
+        events.push(BisyncEvent::Conflict { path });
+    } else if let Some(rest) = message.split("prior lock file found:").nth(1)
+        && let Some(path) = rest.split_whitespace().next()
+    {
+        events.push(BisyncEvent::PriorLockFile {
+            path: path.to_string(),
+        });
+    }
+
+    if lower.contains("cannot find prior path1 or path2 listings")
+        || lower.contains("must run --resync")
+    {
+        events.push(BisyncEvent::ResyncRequired);
+    }
+
+    // `log.Fatalf` output (`Failed to bisync: ...`) carries no level but is fatal.
+    let level = level.or_else(|| {
+        message
+            .starts_with("Failed to ")
+            .then_some(LogLevel::Critical)
+    });
+    if let Some(level) = level.filter(|l| *l >= LogLevel::Error) {
+        events.push(BisyncEvent::Error {
+            level,
+            message: message.to_string(),
+        });
+    } else if events.len() == before {
+        events.push(BisyncEvent::Log {
+            level,
+            message: message.to_string(),
+        });
+    }
+}
+
+/// `Path1:   40 changes:    4 new,   36 newer,    0 older,    0 deleted`
+fn parse_change_summary(message: &str) -> Option<(PathSide, ChangeSummary)> {
+    let (side, rest) = if let Some(rest) = message.strip_prefix("Path1:") {
+        (PathSide::Path1, rest)
+    } else {
+        (PathSide::Path2, message.strip_prefix("Path2:")?)
+    };
+    let (total, counters) = rest.split_once("changes:")?;
+    let mut summary = ChangeSummary {
+        total: total.trim().parse().ok()?,
+        ..Default::default()
+    };
+    apply_counters(&mut summary, counters);
+    Some((side, summary))
+}
+
+fn apply_counters(summary: &mut ChangeSummary, counters: &str) {
+    for part in counters.split(',') {
+        let mut words = part.split_whitespace();
+        let (Some(num), Some(label)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Ok(n) = num.parse::<u32>() else {
+            continue;
+        };
+        match label {
+            "new" => summary.new = n,
+            "modified" => summary.modified = n,
+            "newer" => summary.newer = n,
+            "older" => summary.older = n,
+            "deleted" => summary.deleted = n,
+            _ => {}
+        }
+    }
+}
+
+/// `- Path1    File is new                         - docs/a.txt`
+fn parse_file_change(message: &str) -> Option<PlannedChange> {
+    let rest = message.strip_prefix("- ")?;
+    let (side, rest) = if let Some(rest) = rest.strip_prefix("Path1") {
+        (PathSide::Path1, rest)
+    } else {
+        (PathSide::Path2, rest.strip_prefix("Path2")?)
+    };
+    let rest = rest.trim_start();
+    // Only listing diffs start with "File ..."; "Queue copy to ..." lines describe actions.
+    if !rest.starts_with("File ") {
+        return None;
+    }
+    let (what, path) = rest.split_once(" - ")?;
+    let what = what.trim().to_lowercase();
+    let kind = if what == "file is new" {
+        ChangeKind::New
+    } else if what.contains("deleted") {
+        ChangeKind::Deleted
+    } else if what.contains("newer") {
+        ChangeKind::Newer
+    } else if what.contains("older") {
+        ChangeKind::Older
+    } else {
+        ChangeKind::Modified
+    };
+    Some(PlannedChange {
+        side,
+        kind,
+        path: path.trim().to_string(),
+    })
+}
+
+/// The latest `PathN: ... changes` summary per side.
+pub fn change_summaries(events: &[BisyncEvent]) -> (Option<ChangeSummary>, Option<ChangeSummary>) {
+    let mut path1 = None;
+    let mut path2 = None;
+    for event in events {
+        if let BisyncEvent::Changes { side, summary } = event {
+            match side {
+                PathSide::Path1 => path1 = Some(*summary),
+                PathSide::Path2 => path2 = Some(*summary),
+            }
+        }
+    }
+    (path1, path2)
+}
+
+pub fn planned_changes(events: &[BisyncEvent]) -> Vec<PlannedChange> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            BisyncEvent::FileChange(change) => Some(change.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn resync_required(events: &[BisyncEvent]) -> bool {
+    events
+        .iter()
+        .any(|e| matches!(e, BisyncEvent::ResyncRequired))
+}
+
+pub fn prior_lock_file(events: &[BisyncEvent]) -> Option<&str> {
+    events.iter().find_map(|e| match e {
+        BisyncEvent::PriorLockFile { path } => Some(path.as_str()),
+        _ => None,
+    })
+}
+
+pub fn last_error(events: &[BisyncEvent]) -> Option<&str> {
+    events.iter().rev().find_map(|e| match e {
+        BisyncEvent::Error { message, .. } => Some(message.as_str()),
+        _ => None,
+    })
+}
+
+pub fn stats_blocks(events: &[BisyncEvent]) -> impl Iterator<Item = &StatsBlock> {
+    events.iter().filter_map(|e| match e {
+        BisyncEvent::Stats(block) => Some(block),
+        _ => None,
+    })
+}
+
+/// The most recent JSON stats object, i.e. the totals at the end of the run.
+pub fn last_json_stats(events: &[BisyncEvent]) -> Option<&RcloneStats> {
+    events.iter().rev().find_map(|e| match e {
+        BisyncEvent::JsonStats(stats) => Some(stats),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V158_RESYNC_REQUIRED: &str =
+        include_str!("../tests/fixtures/bisync-synthetic/v1.58-resync-required.log");
+    const V162_CHANGES: &str = include_str!("../tests/fixtures/bisync-synthetic/v1.62-changes.log");
+    const V164_PRIOR_LOCK: &str =
+        include_str!("../tests/fixtures/bisync-synthetic/v1.64-prior-lock.log");
+    const V165_STATS_ONLY: &str =
+        include_str!("../tests/fixtures/bisync-synthetic/v1.65-stats-only.log");
+    const V168_CONFLICT: &str =
+        include_str!("../tests/fixtures/bisync-synthetic/v1.68-conflict.log");
+    const V168_JSON: &str = include_str!("../tests/fixtures/bisync-synthetic/v1.68-json.log");
+
+    fn count(events: &[BisyncEvent], pred: impl Fn(&BisyncEvent) -> bool) -> usize {
+        events.iter().filter(|e| pred(e)).count()
+    }
+
+    #[test]
+    fn splits_timestamp_and_level() {
+        assert_eq!(
+            split_log_prefix("2026/01/11 22:25:26 INFO  : Bisync successful"),
+            (Some(LogLevel::Info), "Bisync successful")
+        );
+        assert_eq!(
+            split_log_prefix("2026/01/11 22:25:26.123456 ERROR : boom"),
+            (Some(LogLevel::Error), "boom")
+        );
+        assert_eq!(
+            split_log_prefix("Transferred:   52 / 262, 20%"),
+            (None, "Transferred:   52 / 262, 20%")
+        );
+    }
+
+    #[test]
+    fn file_changes_ignore_queued_actions() {
+        let events = parse(
+            r#"2026/01/11 22:25:20 INFO  : - Path1    File is newer                       - docs/changed.txt
+2026/01/11 22:25:20 INFO  : - Path1    File changed: size (larger), time (older) - b.bin
+2026/01/11 22:25:21 INFO  : - Path1    Queue copy to Path2                 - gdrive:docs/changed.txt"#,
+        );
+        assert_eq!(
+            planned_changes(&events)
+                .iter()
+                .map(|c| (c.kind, c.path.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ChangeKind::Newer, "docs/changed.txt"),
+                (ChangeKind::Older, "b.bin")
+            ]
+        );
+    }
+
+    #[test]
+    fn v158_resync_required() {
+        let events = parse(V158_RESYNC_REQUIRED);
+        assert!(resync_required(&events));
+        assert!(!events.contains(&BisyncEvent::Success));
+        assert_eq!(
+            last_error(&events),
+            Some("Failed to bisync: bisync aborted")
+        );
+        assert!(count(&events, |e| matches!(e, BisyncEvent::Error { .. })) >= 2);
+    }
+
+    #[test]
+    fn v162_changes_and_file_decisions() {
+        let events = parse(V162_CHANGES);
+        assert!(events.contains(&BisyncEvent::Success));
+        let (p1, p2) = change_summaries(&events);
+        assert_eq!(
+            p1,
+            Some(ChangeSummary {
+                total: 2,
+                new: 1,
+                modified: 0,
+                newer: 1,
+                older: 0,
+                deleted: 0,
+            })
+        );
+        assert_eq!(p2.map(|s| (s.total, s.deleted)), Some((1, 1)));
+
+        let changes = planned_changes(&events);
+        assert_eq!(
+            changes,
+            vec![
+                PlannedChange {
+                    side: PathSide::Path1,
+                    kind: ChangeKind::New,
+                    path: "notes/todo.md".into(),
+                },
+                PlannedChange {
+                    side: PathSide::Path1,
+                    kind: ChangeKind::Newer,
+                    path: "report.odt".into(),
+                },
+                PlannedChange {
+                    side: PathSide::Path2,
+                    kind: ChangeKind::Deleted,
+                    path: "old/scan.pdf".into(),
+                },
+            ]
+        );
+
+        let last = stats_blocks(&events).last().unwrap();
+        assert_eq!(
+            last.transfers,
+            Some(Counter {
+                done: 2,
+                total: 2,
+                percent: Some(100),
+            })
+        );
+        assert_eq!(last.deleted_files, Some(1));
+        assert_eq!(
+            last.bytes.as_ref().map(|b| b.total.as_str()),
+            Some("1.204 MiB")
+        );
+        assert!(!resync_required(&events));
+        assert_eq!(last_error(&events), None);
+    }
+
+    #[test]
+    fn v164_prior_lock_file_is_not_a_resync() {
+        let events = parse(V164_PRIOR_LOCK);
+        assert_eq!(
+            prior_lock_file(&events),
+            Some("/home/u/.cache/rclone/bisync/home_u_Docs..gdrive_Docs.lck")
+        );
+        assert!(!resync_required(&events));
+        assert_eq!(
+            last_error(&events),
+            Some("Failed to bisync: bisync aborted")
+        );
+    }
+
+    #[test]
+    fn v165_stats_blocks_without_info_logging() {
+        let events = parse(V165_STATS_ONLY);
+        let blocks: Vec<_> = stats_blocks(&events).collect();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(
+            blocks[1].transfers.map(|c| (c.done, c.total, c.percent)),
+            Some((52, 262, Some(20)))
+        );
+        let bytes = blocks[1].bytes.as_ref().unwrap();
+        assert_eq!(bytes.speed.as_deref(), Some("2.528 MiB/s"));
+        assert_eq!(bytes.eta.as_deref(), Some("0s"));
+        assert_eq!(blocks[0].bytes.as_ref().unwrap().eta, None);
+        assert_eq!(blocks[3].elapsed.as_deref(), Some("10m22.0s"));
+        assert_eq!(blocks[3].checks.map(|c| c.total), Some(30));
+        assert!(events.contains(&BisyncEvent::Success));
+    }
+
+    #[test]
+    fn v168_conflicts_and_modified_breakdown() {
+        let events = parse(V168_CONFLICT);
+        let (p1, p2) = change_summaries(&events);
+        let p1 = p1.unwrap();
+        assert_eq!(
+            (p1.total, p1.new, p1.modified, p1.newer, p1.older),
+            (2, 1, 1, 1, 0)
+        );
+        assert_eq!(p2.map(|s| (s.total, s.modified)), Some((1, 1)));
+        assert_eq!(
+            events
+                .iter()
+                .filter_map(|e| match e {
+                    BisyncEvent::Conflict { path } => Some(path.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            vec!["report.odt"]
+        );
+        assert_eq!(
+            planned_changes(&events)
+                .iter()
+                .map(|c| c.kind)
+                .collect::<Vec<_>>(),
+            vec![ChangeKind::New, ChangeKind::Newer, ChangeKind::Modified]
+        );
+        assert!(events.contains(&BisyncEvent::Success));
+    }
+
+    #[test]
+    fn v168_json_log() {
+        let events = parse(V168_JSON);
+        assert!(events.contains(&BisyncEvent::Success));
+        assert_eq!(
+            change_summaries(&events).0.map(|s| (s.total, s.new)),
+            Some((2, 1))
+        );
+        assert_eq!(
+            planned_changes(&events)
+                .iter()
+                .map(|c| c.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["notes/todo.md", "report.odt"]
+        );
+        assert_eq!(
+            last_error(&events),
+            Some("locked.db: Failed to copy: permission denied")
+        );
+
+        let progress = events
+            .iter()
+            .find_map(|e| match e {
+                BisyncEvent::JsonStats(s) => Some(s),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(progress.percent(), Some(50));
+        assert_eq!(progress.eta, Some(2.0));
+        assert_eq!(progress.transferring[0].name, "report.odt");
+
+        let last = last_json_stats(&events).unwrap();
+        assert_eq!((last.transfers, last.total_transfers), (2, 2));
+        assert_eq!((last.checks, last.deletes, last.errors), (3, 0, 1));
+        assert_eq!(last.eta, None);
+        assert!(last.transferring.is_empty());
+    }
+
+    #[test]
+    fn renders_json_lines_as_text() {
+        assert_eq!(
+            render_line(
+                r#"{"time":"2026-01-11T22:25:26.123456+01:00","level":"info","msg":"Copied (new)","object":"notes/todo.md","source":"operations/copy.go:361"}"#
+            ),
+            "2026/01/11 22:25:26 INFO  : notes/todo.md: Copied (new)"
+        );
+        assert_eq!(render_line("plain text"), "plain text");
+    }
+}
@@ -1,4 +1,6 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::runner::{QueueMode, Trigger};
 
 #[derive(Debug, Parser)]
 #[command(name = "rclone_sync_helper")]
@@ -10,6 +12,16 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Output format; json prints one serde-serialized document per command for scripts
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
 }
 
 #[derive(Debug, Subcommand)]
@@ -22,6 +34,68 @@ pub enum Commands {
         /// Job name (config: $XDG_CONFIG_HOME/io/rclone/sync-helper/jobs/<job>.toml)
         #[arg(long, default_value = "default")]
         job: String,
+
+        /// Run every job in the jobs dir and print a summary
+        #[arg(long, conflicts_with_all = ["job", "pair"])]
+        all: bool,
+
+        /// Number of jobs to run at the same time with --all
+        #[arg(long, default_value_t = 1, requires = "all")]
+        concurrency: usize,
+
+        /// Run only one pair, given by its 1-based index or its local path
+        #[arg(long)]
+        pair: Option<String>,
+
+        /// Run bisync with --resync to rebuild the listings (recovery)
+        #[arg(long)]
+        resync: bool,
+
+        /// Pass --dry-run to every pair and print the changes bisync would make
+        #[arg(long)]
+        dry_run: bool,
+
+        /// What started this run (recorded in the job lock)
+        #[arg(long, value_enum, default_value_t = Trigger::Manual)]
+        trigger: Trigger,
+
+        /// What to do if the job is already running: skip this run, wait for the lock, or
+        /// coalesce into a single follow-up run
+        #[arg(long, value_enum, default_value_t = QueueMode::Skip)]
+        queue: QueueMode,
+    },
+
+    /// Cancel the sync currently running for a job
+    Cancel {
+        #[arg(long, default_value = "default")]
+        job: String,
+    },
+
+    /// Show recent runs of a job, newest first
+    History {
+        #[arg(long, default_value = "default")]
+        job: String,
+
+        /// Print the records as JSON (same as --output json)
+        #[arg(long)]
+        json: bool,
+
+        /// Number of runs to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Check rclone, systemd and the job setup, with hints for anything that's wrong
+    Doctor {
+        /// Only check this job (default: every job)
+        #[arg(long)]
+        job: Option<String>,
+    },
+
+    /// Manage job configs
+    Jobs {
+        #[command(subcommand)]
+        command: JobsCommands,
     },
 
     /// Manage the per-job systemd --user timer/service
@@ -31,10 +105,72 @@ pub enum Commands {
     },
 }
 
+#[derive(Debug, Subcommand)]
+pub enum JobsCommands {
+    /// List configured jobs with their last run
+    List,
+
+    /// Print a job's config, resolved pairs and the files it uses
+    Show {
+        #[arg(long)]
+        job: String,
+    },
+
+    /// Create a job after checking that the local folder and the remote exist.
+    /// Without --local/--remote, lists the configured rclone remotes
+    Create {
+        #[arg(long)]
+        job: String,
+
+        /// Local folder to sync
+        #[arg(long)]
+        local: Option<String>,
+
+        /// Remote to sync with, e.g. gdrive:Documents
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// rclone config file to use instead of rclone's default
+        #[arg(long)]
+        rclone_config: Option<String>,
+
+        /// Run an initial sync with --resync once the job is saved
+        #[arg(long)]
+        resync: bool,
+    },
+
+    /// Delete a job with its status, history, systemd units and default log directory
+    Delete {
+        #[arg(long)]
+        job: String,
+
+        /// Keep the job's log directory
+        #[arg(long)]
+        keep_logs: bool,
+    },
+
+    /// Rename a job, moving its status, history, systemd units and default log directory
+    Rename {
+        #[arg(long)]
+        job: String,
+
+        /// New job name
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Check a job's config, local folders and remotes; exits non-zero on problems
+    Validate {
+        /// Job to check (default: every job)
+        #[arg(long)]
+        job: Option<String>,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 pub enum SystemdCommands {
     /// Create/update the unit files for a job (does not enable automatically)
-    /// Timer runs hourly on the hour (10:00, 11:00, 12:00, etc.)
+    /// The timer follows the job's [schedule] (default: hourly on the hour)
     Install {
         #[arg(long, default_value = "default")]
         job: String,
@@ -54,4 +190,27 @@ pub enum SystemdCommands {
         #[arg(long, default_value = "default")]
         job: String,
     },
+
+    /// Stop and disable the timer, then delete the job's units (also for deleted jobs)
+    Uninstall {
+        #[arg(long, default_value = "default")]
+        job: String,
+    },
+
+    /// Rewrite the job's unit files if they differ from what install would write now
+    /// (e.g. after the binary moved)
+    Repair {
+        #[arg(long, default_value = "default")]
+        job: String,
+    },
+
+    /// Check the job's [schedule] with systemd-analyze and print when the timer would run next
+    Schedule {
+        #[arg(long, default_value = "default")]
+        job: String,
+
+        /// Number of upcoming runs to show
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+    },
 }
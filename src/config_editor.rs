@@ -0,0 +1,410 @@
+use anyhow::Result;
+
+use crate::job_config::{self, JobConfig, Resources, Schedule, SyncPair};
+use crate::systemd;
+
+/// Editable copy of a job config, as shown in the applet's settings view.
+///
+/// Optional fields are kept as plain strings (empty means unset) and `extra_args` as a single
+/// shell-like line, so the text inputs can bind to them directly.
+#[derive(Debug, Clone)]
+pub struct ConfigDraft {
+    /// Config the draft was loaded from; fields the editor doesn't know about are kept as-is.
+    base: JobConfig,
+    pub local_path: String,
+    pub remote: String,
+    pub pairs: Vec<SyncPair>,
+    pub extra_args: String,
+    pub rclone_config_path: String,
+    pub lock_file: String,
+    pub log_dir: String,
+    pub auto_resync: bool,
+    pub clean_bisync_locks: bool,
+    pub use_nice_ionice: bool,
+    pub sync_now_via_systemd: bool,
+    pub on_calendar: String,
+    pub interval: String,
+    pub randomized_delay: String,
+    pub on_boot: String,
+    pub on_startup: String,
+    pub persistent: bool,
+    pub nice: String,
+    pub cpu_weight: String,
+    pub io_weight: String,
+    pub memory_max: String,
+    pub timeout: String,
+    pub wait_for_network: bool,
+    pub require_ac_power: bool,
+    /// Next elapse times from the last "Check schedule" (empty for an interval), or why the
+    /// schedule is invalid.
+    pub schedule_preview: Option<Result<Vec<String>, String>>,
+    /// Validation or save error from the last `save` attempt.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum EditMessage {
+    LocalPath(String),
+    Remote(String),
+    PairLocal(usize, String),
+    PairRemote(usize, String),
+    AddPair,
+    RemovePair(usize),
+    MovePairUp(usize),
+    MovePairDown(usize),
+    ExtraArgs(String),
+    RcloneConfigPath(String),
+    LockFile(String),
+    LogDir(String),
+    AutoResync(bool),
+    CleanBisyncLocks(bool),
+    UseNiceIonice(bool),
+    SyncNowViaSystemd(bool),
+    OnCalendar(String),
+    Interval(String),
+    RandomizedDelay(String),
+    OnBoot(String),
+    OnStartup(String),
+    Persistent(bool),
+    Nice(String),
+    CpuWeight(String),
+    IoWeight(String),
+    MemoryMax(String),
+    Timeout(String),
+    WaitForNetwork(bool),
+    RequireAcPower(bool),
+}
+
+impl ConfigDraft {
+    pub fn load(job: &str) -> Result<Self> {
+        Ok(Self::from_config(job_config::load_or_create_job(job)?))
+    }
+
+    pub fn from_config(cfg: JobConfig) -> Self {
+        Self {
+            local_path: cfg.local_path.clone(),
+            remote: cfg.remote.clone(),
+            pairs: cfg.pairs.clone(),
+            extra_args: join_args(&cfg.extra_args),
+            rclone_config_path: cfg.rclone_config_path.clone().unwrap_or_default(),
+            lock_file: cfg.lock_file.clone().unwrap_or_default(),
+            log_dir: cfg.log_dir.clone().unwrap_or_default(),
+            auto_resync: cfg.auto_resync,
+            clean_bisync_locks: cfg.clean_bisync_locks,
+            use_nice_ionice: cfg.use_nice_ionice,
+            sync_now_via_systemd: cfg.sync_now_via_systemd,
+            on_calendar: cfg.schedule.on_calendar.clone(),
+            interval: cfg.schedule.interval.clone().unwrap_or_default(),
+            randomized_delay: cfg.schedule.randomized_delay.clone().unwrap_or_default(),
+            on_boot: cfg.schedule.on_boot.clone().unwrap_or_default(),
+            on_startup: cfg.schedule.on_startup.clone().unwrap_or_default(),
+            persistent: cfg.schedule.persistent,
+            nice: number_field(cfg.resources.nice),
+            cpu_weight: number_field(cfg.resources.cpu_weight),
+            io_weight: number_field(cfg.resources.io_weight),
+            memory_max: cfg.resources.memory_max.clone().unwrap_or_default(),
+            timeout: cfg.resources.timeout.clone().unwrap_or_default(),
+            wait_for_network: cfg.resources.wait_for_network,
+            require_ac_power: cfg.resources.require_ac_power,
+            schedule_preview: None,
+            error: None,
+            base: cfg,
+        }
+    }
+
+    pub fn job(&self) -> &str {
+        &self.base.name
+    }
+
+    pub fn update(&mut self, msg: EditMessage) {
+        match msg {
+            EditMessage::LocalPath(v) => self.local_path = v,
+            EditMessage::Remote(v) => self.remote = v,
+            EditMessage::PairLocal(idx, v) => {
+                if let Some(pair) = self.pairs.get_mut(idx) {
+                    pair.local = v;
+                }
+            }
+            EditMessage::PairRemote(idx, v) => {
+                if let Some(pair) = self.pairs.get_mut(idx) {
+                    pair.remote = v;
+                }
+            }
+            EditMessage::AddPair => self.pairs.push(SyncPair {
+                local: String::new(),
+                remote: String::new(),
+            }),
+            EditMessage::RemovePair(idx) => {
+                if idx < self.pairs.len() {
+                    self.pairs.remove(idx);
+                }
+            }
+            EditMessage::MovePairUp(idx) => {
+                if idx > 0 && idx < self.pairs.len() {
+                    self.pairs.swap(idx - 1, idx);
+                }
+            }
+            EditMessage::MovePairDown(idx) => {
+                if idx + 1 < self.pairs.len() {
+                    self.pairs.swap(idx, idx + 1);
+                }
+            }
+            EditMessage::ExtraArgs(v) => self.extra_args = v,
+            EditMessage::RcloneConfigPath(v) => self.rclone_config_path = v,
+            EditMessage::LockFile(v) => self.lock_file = v,
+            EditMessage::LogDir(v) => self.log_dir = v,
+            EditMessage::AutoResync(v) => self.auto_resync = v,
+            EditMessage::CleanBisyncLocks(v) => self.clean_bisync_locks = v,
+            EditMessage::UseNiceIonice(v) => self.use_nice_ionice = v,
+            EditMessage::SyncNowViaSystemd(v) => self.sync_now_via_systemd = v,
+            EditMessage::OnCalendar(v) => self.on_calendar = v,
+            EditMessage::Interval(v) => self.interval = v,
+            EditMessage::RandomizedDelay(v) => self.randomized_delay = v,
+            EditMessage::OnBoot(v) => self.on_boot = v,
+            EditMessage::OnStartup(v) => self.on_startup = v,
+            EditMessage::Persistent(v) => self.persistent = v,
+            EditMessage::Nice(v) => self.nice = v,
+            EditMessage::CpuWeight(v) => self.cpu_weight = v,
+            EditMessage::IoWeight(v) => self.io_weight = v,
+            EditMessage::MemoryMax(v) => self.memory_max = v,
+            EditMessage::Timeout(v) => self.timeout = v,
+            EditMessage::WaitForNetwork(v) => self.wait_for_network = v,
+            EditMessage::RequireAcPower(v) => self.require_ac_power = v,
+        }
+        self.error = None;
+        self.schedule_preview = None;
+    }
+
+    pub fn schedule(&self) -> Schedule {
+        let optional = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        Schedule {
+            on_calendar: self.on_calendar.trim().to_string(),
+            interval: optional(&self.interval),
+            randomized_delay: optional(&self.randomized_delay),
+            on_boot: optional(&self.on_boot),
+            on_startup: optional(&self.on_startup),
+            persistent: self.persistent,
+        }
+    }
+
+    /// The resource settings, or which number field doesn't parse.
+    pub fn resources(&self) -> Result<Resources> {
+        let optional = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        Ok(Resources {
+            nice: parse_number("Nice", &self.nice)?,
+            cpu_weight: parse_number("CPU weight", &self.cpu_weight)?,
+            io_weight: parse_number("IO weight", &self.io_weight)?,
+            memory_max: optional(&self.memory_max),
+            timeout: optional(&self.timeout),
+            wait_for_network: self.wait_for_network,
+            require_ac_power: self.require_ac_power,
+            ..self.base.resources.clone()
+        })
+    }
+
+    /// Whether saving would change the schedule, so installed units need rewriting.
+    pub fn schedule_changed(&self) -> bool {
+        self.schedule() != self.base.schedule
+    }
+
+    /// Whether saving would change what the installed units contain.
+    pub fn units_changed(&self) -> bool {
+        self.schedule_changed()
+            || self
+                .resources()
+                .is_ok_and(|resources| resources != self.base.resources)
+    }
+
+    /// Validate the schedule and fill `schedule_preview` with its next elapse times.
+    pub fn check_schedule(&mut self, count: usize) {
+        let schedule = self.schedule();
+        let res = systemd::validate_schedule(&schedule).and_then(|_| match schedule.interval() {
+            Some(_) => Ok(vec![]),
+            None => systemd::calendar_next_elapses(&schedule.on_calendar, count),
+        });
+        self.schedule_preview = Some(res.map_err(|err| format!("{err:#}")));
+    }
+
+    /// Build the config this draft describes. Pair rows left completely empty are dropped.
+    pub fn to_config(&self) -> JobConfig {
+        let optional = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        JobConfig {
+            local_path: self.local_path.trim().to_string(),
+            remote: self.remote.trim().to_string(),
+            pairs: self
+                .pairs
+                .iter()
+                .filter(|p| !p.local.trim().is_empty() || !p.remote.trim().is_empty())
+                .map(|p| SyncPair {
+                    local: p.local.trim().to_string(),
+                    remote: p.remote.trim().to_string(),
+                })
+                .collect(),
+            // `pairs` now holds whatever the deprecated `directories` migrated to.
+            directories: vec![],
+            extra_args: split_args(&self.extra_args),
+            rclone_config_path: optional(&self.rclone_config_path),
+            lock_file: optional(&self.lock_file),
+            log_dir: optional(&self.log_dir),
+            auto_resync: self.auto_resync,
+            clean_bisync_locks: self.clean_bisync_locks,
+            use_nice_ionice: self.use_nice_ionice,
+            sync_now_via_systemd: self.sync_now_via_systemd,
+            schedule: self.schedule(),
+            // `save` reports unparsable numbers before this is used.
+            resources: self
+                .resources()
+                .unwrap_or_else(|_| self.base.resources.clone()),
+            ..self.base.clone()
+        }
+    }
+
+    /// Validate with the same rules as a run (and a changed schedule or resource limits with
+    /// `systemd-analyze`), then write the job file. On failure the error is kept on the draft for
+    /// the editor to show.
+    pub fn save(&mut self) -> Result<()> {
+        let cfg = self.to_config();
+        let res = crate::runner::validate_config(&cfg)
+            .and_then(|_| {
+                if self.schedule_changed() {
+                    systemd::validate_schedule(&cfg.schedule)?;
+                }
+                if self.resources()? != self.base.resources {
+                    systemd::validate_resources(&cfg.resources)?;
+                }
+                Ok(())
+            })
+            .and_then(|_| job_config::save_job(&cfg));
+        if let Err(err) = &res {
+            self.error = Some(format!("{err:#}"));
+        } else {
+            self.base = cfg;
+        }
+        res
+    }
+}
+
+fn number_field<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Parse an optional number field; empty means unset.
+fn parse_number<T: std::str::FromStr>(what: &str, value: &str) -> Result<Option<T>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse() {
+        Ok(n) => Ok(Some(n)),
+        Err(_) => anyhow::bail!("{what} must be a whole number, not '{value}'"),
+    }
+}
+
+/// Split a line of arguments on whitespace, keeping double-quoted parts together. `\"` and `\\`
+/// stand for a literal quote and backslash; any other backslash is kept as is.
+fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('"' | '\\')) => {
+                current.extend(chars.next());
+                in_arg = true;
+            }
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let escaped = arg.replace('\\', "\\\\").replace('"', "\\\"");
+            if arg.is_empty() || arg.chars().any(char::is_whitespace) {
+                format!("\"{escaped}\"")
+            } else {
+                escaped
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_args_round_trip_through_the_text_field() {
+        let args = vec![
+            "--exclude".to_string(),
+            "My Documents/**".to_string(),
+            "--fast-list".to_string(),
+        ];
+        let line = join_args(&args);
+        assert_eq!(line, "--exclude \"My Documents/**\" --fast-list");
+        assert_eq!(split_args(&line), args);
+        assert_eq!(split_args("  --a   \"\" b "), vec!["--a", "", "b"]);
+
+        let args = vec![
+            "--filter=+ \"a b\"".to_string(),
+            "--exclude=*\"x\"*".to_string(),
+            "--include=C:\\dir\\".to_string(),
+        ];
+        let line = join_args(&args);
+        assert_eq!(
+            line,
+            "\"--filter=+ \\\"a b\\\"\" --exclude=*\\\"x\\\"* --include=C:\\\\dir\\\\"
+        );
+        assert_eq!(split_args(&line), args);
+        assert_eq!(split_args("--regex=\\d+"), vec!["--regex=\\d+"]);
+    }
+
+    #[test]
+    fn pairs_can_be_reordered_and_empty_rows_are_dropped() {
+        let mut draft = ConfigDraft::from_config(JobConfig::empty("docs"));
+        draft.local_path = "/home/me".into();
+        draft.remote = "gdrive:".into();
+        for (local, remote) in [("Documents", "Documents"), ("Pictures", "Photos")] {
+            draft.update(EditMessage::AddPair);
+            let idx = draft.pairs.len() - 1;
+            draft.update(EditMessage::PairLocal(idx, local.into()));
+            draft.update(EditMessage::PairRemote(idx, remote.into()));
+        }
+        draft.update(EditMessage::AddPair);
+        draft.update(EditMessage::MovePairDown(0));
+        draft.update(EditMessage::MovePairUp(0));
+        draft.update(EditMessage::LogDir("  ".into()));
+
+        let cfg = draft.to_config();
+        let locals: Vec<&str> = cfg.pairs.iter().map(|p| p.local.as_str()).collect();
+        assert_eq!(locals, vec!["Pictures", "Documents"]);
+        assert_eq!(cfg.log_dir, None);
+        assert!(crate::runner::validate_config(&cfg).is_ok());
+
+        draft.update(EditMessage::RemovePair(0));
+        draft.update(EditMessage::RemovePair(0));
+        draft.update(EditMessage::Remote(String::new()));
+        assert!(crate::runner::validate_config(&draft.to_config()).is_err());
+    }
+}
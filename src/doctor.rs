@@ -0,0 +1,356 @@
+use std::fs;
+
+use serde::Serialize;
+
+use crate::job_config::{self, JobConfig};
+use crate::rclone;
+use crate::runner;
+use crate::systemd::{self, SystemdUser};
+
+/// rclone versions before this lack bisync fixes we rely on (`--resilient`, recovery, JSON stats).
+const RECOMMENDED_RCLONE: (u32, u32, u32) = (1, 66, 0);
+/// bisync first shipped in this release.
+const MIN_RCLONE: (u32, u32, u32) = (1, 58, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Check the environment and the given jobs (every configured job if `jobs` is empty).
+pub fn run(jobs: &[String]) -> Vec<Check> {
+    let mut checks = vec![check_rclone()];
+    checks.extend(check_priority_wrappers());
+    let systemd_ok = match systemd::user_manager_reachable() {
+        Ok(()) => {
+            checks.push(Check::pass(
+                "systemd",
+                "systemd user manager is reachable over D-Bus",
+            ));
+            true
+        }
+        Err(err) => {
+            checks.push(Check::warn(
+                "systemd",
+                format!(
+                    "systemd user manager is not reachable over D-Bus: {}",
+                    err.to_string().trim()
+                ),
+                "Timers need a systemd user session; run from a logged-in desktop session or enable lingering (loginctl enable-linger).",
+            ));
+            false
+        }
+    };
+    checks.push(check_stale_bisync_locks());
+
+    let jobs = if jobs.is_empty() {
+        job_config::list_jobs().unwrap_or_default()
+    } else {
+        jobs.to_vec()
+    };
+    if jobs.is_empty() {
+        checks.push(Check::warn(
+            "jobs",
+            "No jobs configured",
+            "Create one with `rclone_sync_helper jobs create` or the + button in the applet.",
+        ));
+    }
+    checks.push(check_orphaned_units());
+    for job in &jobs {
+        match crate::jobs::load_existing(job) {
+            Ok(cfg) => checks.extend(check_job(&cfg, systemd_ok)),
+            Err(err) => checks.push(Check::fail(
+                format!("{job}: config"),
+                format!("{err:#}"),
+                "Fix the TOML file or recreate the job.",
+            )),
+        }
+    }
+    checks
+}
+
+fn check_rclone() -> Check {
+    let fmt = |(a, b, c): (u32, u32, u32)| format!("{a}.{b}.{c}");
+    match rclone::version() {
+        Ok(v) if v < MIN_RCLONE => Check::fail(
+            "rclone",
+            format!("rclone {} has no bisync", fmt(v)),
+            format!(
+                "Install rclone {} or newer from https://rclone.org/downloads/",
+                fmt(RECOMMENDED_RCLONE)
+            ),
+        ),
+        Ok(v) if v < RECOMMENDED_RCLONE => Check::warn(
+            "rclone",
+            format!(
+                "rclone {} is older than {}",
+                fmt(v),
+                fmt(RECOMMENDED_RCLONE)
+            ),
+            "Older bisync releases need --resync more often and lack recovery options; upgrade rclone.",
+        ),
+        Ok(v) => Check::pass("rclone", format!("rclone {}", fmt(v))),
+        Err(err) => Check::fail(
+            "rclone",
+            format!("{err:#}"),
+            "Install rclone and make sure it is on PATH for your session and systemd --user.",
+        ),
+    }
+}
+
+fn check_priority_wrappers() -> Vec<Check> {
+    ["nice", "ionice"]
+        .into_iter()
+        .map(|cmd| {
+            if runner::cmd_exists(cmd) {
+                Check::pass(cmd, format!("{cmd} is available"))
+            } else {
+                Check::warn(
+                    cmd,
+                    format!("{cmd} not found; rclone runs at normal priority"),
+                    format!("Install {cmd} (util-linux/coreutils) or set use_nice_ionice = false."),
+                )
+            }
+        })
+        .collect()
+}
+
+fn check_stale_bisync_locks() -> Check {
+    match runner::stale_bisync_locks() {
+        Ok(stale) if stale.is_empty() => Check::pass("bisync locks", "No stale .lck files"),
+        Ok(stale) => Check::warn(
+            "bisync locks",
+            format!(
+                "Stale lock files: {}",
+                stale
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            "They are removed before the next run when clean_bisync_locks is on; otherwise delete them.",
+        ),
+        Err(err) => Check::warn(
+            "bisync locks",
+            format!("Could not check ~/.cache/rclone/bisync: {err:#}"),
+            "Check that HOME is set and the directory is readable.",
+        ),
+    }
+}
+
+fn check_job(cfg: &JobConfig, systemd_ok: bool) -> Vec<Check> {
+    let job = &cfg.name;
+    let name = |what: &str| format!("{job}: {what}");
+    let mut checks = Vec::new();
+
+    if let Err(err) = runner::validate_config(cfg) {
+        checks.push(Check::fail(
+            name("config"),
+            err.to_string(),
+            "Set local_path and remote (or pairs) in the job config or the applet's settings.",
+        ));
+        return checks;
+    }
+
+    // rclone config file and the remotes the pairs refer to.
+    let config_path = cfg.rclone_config_path.as_deref();
+    match rclone::config_file(config_path) {
+        Ok(path) => match fs::read_to_string(&path) {
+            Ok(_) => checks.push(Check::pass(
+                name("rclone config"),
+                format!("{} is readable", path.display()),
+            )),
+            Err(err) => checks.push(Check::fail(
+                name("rclone config"),
+                format!("Cannot read {}: {err}", path.display()),
+                "Run `rclone config` to create it, or fix rclone_config_path and the file's permissions.",
+            )),
+        },
+        Err(err) => checks.push(Check::fail(
+            name("rclone config"),
+            format!("{err:#}"),
+            "Check rclone_config_path and that rclone runs.",
+        )),
+    }
+    let pairs = runner::resolved_pairs(cfg);
+    let mut wanted: Vec<String> = pairs
+        .iter()
+        .filter_map(|(_, remote)| rclone::remote_name(remote))
+        .collect();
+    wanted.sort();
+    wanted.dedup();
+    if !wanted.is_empty() {
+        match rclone::list_remotes(config_path) {
+            Ok(remotes) => {
+                let missing: Vec<&String> =
+                    wanted.iter().filter(|r| !remotes.contains(r)).collect();
+                if missing.is_empty() {
+                    checks.push(Check::pass(
+                        name("remotes"),
+                        format!("Configured: {}", wanted.join(", ")),
+                    ));
+                } else {
+                    checks.push(Check::fail(
+                        name("remotes"),
+                        format!(
+                            "Not in rclone's config: {}",
+                            missing
+                                .iter()
+                                .map(|r| r.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        "Add the remote with `rclone config` or fix the remote in the job.",
+                    ));
+                }
+            }
+            Err(err) => checks.push(Check::fail(
+                name("remotes"),
+                format!("{err:#}"),
+                "Check that rclone can read its config.",
+            )),
+        }
+    }
+
+    for (local, _) in &pairs {
+        let path = job_config::expand_home(local);
+        if path.is_dir() {
+            checks.push(Check::pass(name("local path"), format!("{local} exists")));
+        } else {
+            checks.push(Check::fail(
+                name("local path"),
+                format!("{local} does not exist or is not a folder"),
+                "Create the folder or fix local_path/pairs in the job.",
+            ));
+        }
+    }
+
+    if systemd_ok {
+        checks.push(check_units(job));
+    }
+    checks.push(check_job_lock(cfg));
+    checks
+}
+
+fn check_units(job: &str) -> Check {
+    let name = format!("{job}: systemd units");
+    let sd = match SystemdUser::new() {
+        Ok(sd) => sd,
+        Err(err) => return Check::warn(name, format!("{err:#}"), "Check XDG_CONFIG_HOME."),
+    };
+    if !sd.units_installed(job) {
+        return Check::warn(
+            name,
+            "Not installed; the job only runs when started by hand",
+            format!("rclone_sync_helper systemd install --job {job}"),
+        );
+    }
+    match sd.outdated_units(job) {
+        Ok(outdated) if outdated.is_empty() => Check::pass(name, "Installed and up to date"),
+        Ok(outdated) => Check::warn(
+            name,
+            format!("Out of date: {}", outdated.join(", ")),
+            format!("Rewrite them with `rclone_sync_helper systemd repair --job {job}`."),
+        ),
+        Err(err) => Check::warn(name, format!("{err:#}"), "Reinstall the units."),
+    }
+}
+
+/// Units left behind by jobs whose config is gone.
+fn check_orphaned_units() -> Check {
+    let name = "systemd units";
+    let orphaned = SystemdUser::new().and_then(|sd| {
+        let jobs = job_config::list_jobs()?;
+        Ok(sd
+            .installed_jobs()?
+            .into_iter()
+            .filter(|job| !jobs.contains(job))
+            .collect::<Vec<_>>())
+    });
+    match orphaned {
+        Ok(orphaned) if orphaned.is_empty() => Check::pass(name, "No units for deleted jobs"),
+        Ok(orphaned) => Check::warn(
+            name,
+            format!("Units for deleted jobs: {}", orphaned.join(", ")),
+            format!(
+                "Remove them with `rclone_sync_helper systemd uninstall --job {}`.",
+                orphaned[0]
+            ),
+        ),
+        Err(err) => Check::warn(name, format!("{err:#}"), "Check XDG_CONFIG_HOME."),
+    }
+}
+
+fn check_job_lock(cfg: &JobConfig) -> Check {
+    let name = format!("{}: lock", cfg.name);
+    let path = runner::lock_file_path(cfg);
+    if let Some(info) = runner::detect_running(&path) {
+        return Check::pass(
+            name,
+            format!(
+                "Running now (PID {}, started by {})",
+                info.pid,
+                info.trigger.label()
+            ),
+        );
+    }
+    match path.parent().map(|dir| fs::create_dir_all(dir).is_ok()) {
+        Some(true) => Check::pass(name, format!("{} is free", path.display())),
+        _ => Check::fail(
+            name,
+            format!("Cannot create {}", path.display()),
+            "Set lock_file to a writable location.",
+        ),
+    }
+}
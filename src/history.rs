@@ -0,0 +1,184 @@
+//! Append-only run history, one JSON record per line in `<state_dir>/history/<job>.jsonl`.
+//!
+//! `status.json` only holds the latest run; the history keeps enough of every run to see when a
+//! job started failing. Files are trimmed to the newest [`MAX_RECORDS`] entries.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{RunOutcome, Trigger};
+use crate::status::{self, ScriptResult};
+
+const HISTORY_DIR_NAME: &str = "history";
+/// Records kept per job.
+const MAX_RECORDS: usize = 500;
+/// Extra records tolerated before the file is rewritten, so trimming doesn't happen on every run.
+const TRIM_SLACK: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub job: String,
+    pub trigger: Trigger,
+    pub started_at: DateTime<Utc>,
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+    pub exit_code: i32,
+    pub outcome: RunOutcome,
+    #[serde(default)]
+    pub changed_count: Option<u32>,
+    #[serde(default)]
+    pub log_file: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl HistoryRecord {
+    pub fn from_result(job: &str, result: &ScriptResult) -> Self {
+        Self {
+            job: job.to_string(),
+            trigger: result.trigger,
+            started_at: result.timestamp,
+            duration_secs: result.duration_secs,
+            exit_code: result.exit_code,
+            outcome: result.outcome,
+            changed_count: result.changed_count(),
+            log_file: result.log_file.clone(),
+            error: match result.outcome {
+                RunOutcome::Failed | RunOutcome::Skipped => result.error_summary(),
+                RunOutcome::Success | RunOutcome::Cancelled => None,
+            },
+        }
+    }
+}
+
+pub fn history_file_path(job: &str) -> Result<PathBuf> {
+    let dir = status::state_dir()?.join(HISTORY_DIR_NAME);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir.join(format!("{job}.jsonl")))
+}
+
+/// Lock file serialising writes to a history file.
+pub fn lock_file_path(history_file: &Path) -> PathBuf {
+    let mut name = history_file.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+pub fn append(record: &HistoryRecord) -> Result<()> {
+    append_to(&history_file_path(&record.job)?, record, MAX_RECORDS)
+}
+
+/// The newest `limit` runs of `job`, newest first.
+pub fn load(job: &str, limit: usize) -> Result<Vec<HistoryRecord>> {
+    let mut records = read_from(&history_file_path(job)?)?;
+    records.reverse();
+    records.truncate(limit);
+    Ok(records)
+}
+
+fn append_to(path: &Path, record: &HistoryRecord, max_records: usize) -> Result<()> {
+    // Runs of the same job can finish together (e.g. one skipped while another ends). The trim
+    // replaces the file, so appends and trims are serialised on a separate lock file whose inode
+    // stays put.
+    let lock_path = lock_file_path(path);
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+    lock.lock()
+        .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    // One write per record keeps concurrent appends from different jobs' runs line-atomic.
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    drop(file);
+
+    let records = read_from(path)?;
+    if records.len() > max_records + TRIM_SLACK {
+        let keep = &records[records.len() - max_records..];
+        let mut content = String::new();
+        for record in keep {
+            content.push_str(&serde_json::to_string(record)?);
+            content.push('\n');
+        }
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)?;
+    }
+    // The lock is released when `lock` is closed.
+    Ok(())
+}
+
+/// All records in file order (oldest first). Lines that don't parse are skipped.
+fn read_from(path: &Path) -> Result<Vec<HistoryRecord>> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(minute: u32, outcome: RunOutcome) -> HistoryRecord {
+        HistoryRecord {
+            job: "docs".into(),
+            trigger: Trigger::Timer,
+            started_at: Utc.with_ymd_and_hms(2026, 1, 5, 12, minute, 0).unwrap(),
+            duration_secs: Some(3),
+            exit_code: 0,
+            outcome,
+            changed_count: Some(1),
+            log_file: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn appends_and_trims_to_newest_records() {
+        let dir = std::env::temp_dir().join(format!("rclone-sync-history-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("docs.jsonl");
+
+        for minute in 0..(3 + TRIM_SLACK as u32 + 1) {
+            append_to(&path, &record(minute, RunOutcome::Success), 3).unwrap();
+        }
+        let records = read_from(&path).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].started_at.format("%M").to_string(), "53");
+
+        // A torn or foreign line doesn't hide the rest of the history.
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{not json\n")
+            .unwrap();
+        append_to(&path, &record(59, RunOutcome::Skipped), 3).unwrap();
+        let records = read_from(&path).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[3].outcome, RunOutcome::Skipped);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
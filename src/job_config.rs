@@ -24,10 +24,11 @@ pub struct JobConfig {
     /// Deprecated: older configs used `directories` instead of `pairs`.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub directories: Vec<String>,
-    /// Lock file to prevent concurrent runs. If not set, defaults to `/tmp/rclone-sync.lock`.
+    /// Lock file to prevent concurrent runs. If not set, defaults to
+    /// `$XDG_RUNTIME_DIR/rclone-sync-helper/<job>.lock`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub lock_file: Option<String>,
-    /// Log directory for per-run log files. If not set, defaults to `$HOME/logs/rclone-sync`.
+    /// Log directory for per-run log files. If not set, defaults to `$HOME/logs/rclone-sync/<job>`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub log_dir: Option<String>,
     /// Attempt a second run with `--resync` when bisync indicates recovery is required.
@@ -36,9 +37,19 @@ pub struct JobConfig {
     /// Remove stale bisync `.lck` files under `$HOME/.cache/rclone/bisync` before starting.
     #[serde(default = "default_true", skip_serializing_if = "is_true")]
     pub clean_bisync_locks: bool,
-    /// Run rclone under low CPU/IO priority if `nice` and `ionice` exist.
+    /// Run rclone under low CPU/IO priority if `nice` and `ionice` exist. Runs in our systemd
+    /// units use `resources` instead.
     #[serde(default = "default_true", skip_serializing_if = "is_true")]
     pub use_nice_ionice: bool,
+    /// Run the applet's "Sync now" as a systemd --user unit, so it survives the panel restarting.
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub sync_now_via_systemd: bool,
+    /// When the systemd timer runs the job.
+    #[serde(default, skip_serializing_if = "Schedule::is_default")]
+    pub schedule: Schedule,
+    /// Limits and conditions for runs of the systemd service.
+    #[serde(default, skip_serializing_if = "Resources::is_default")]
+    pub resources: Resources,
 }
 
 impl JobConfig {
@@ -56,8 +67,160 @@ impl JobConfig {
             auto_resync: true,
             clean_bisync_locks: true,
             use_nice_ionice: true,
+            sync_now_via_systemd: true,
+            schedule: Schedule::default(),
+            resources: Resources::default(),
+        }
+    }
+}
+
+/// The `[schedule]` section of a job: what the generated systemd timer triggers on.
+/// Durations use systemd's time span syntax (`30min`, `2h 15min`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Schedule {
+    /// `OnCalendar=` expression, e.g. `hourly` or `Mon..Fri 09:00`. Ignored if `interval` is set.
+    pub on_calendar: String,
+    /// Run this long after the previous run started (`OnUnitActiveSec=`) instead of on a calendar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    /// Delay each run by a random time up to this long (`RandomizedDelaySec=`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub randomized_delay: Option<String>,
+    /// Also run this long after boot (`OnBootSec=`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_boot: Option<String>,
+    /// Also run this long after the user's systemd instance started, i.e. after login
+    /// (`OnStartupSec=`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_startup: Option<String>,
+    /// Catch up on a calendar run missed while the machine was off (`Persistent=`).
+    pub persistent: bool,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            on_calendar: "hourly".into(),
+            interval: None,
+            randomized_delay: None,
+            on_boot: None,
+            on_startup: None,
+            persistent: true,
+        }
+    }
+}
+
+impl Schedule {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The interval, if the schedule uses one instead of a calendar expression.
+    pub fn interval(&self) -> Option<&str> {
+        self.interval
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Short description for the timer unit, e.g. `hourly`, `every 30min` or `on Mon 09:00`.
+    pub fn describe(&self) -> String {
+        let calendar = self.on_calendar.trim();
+        match self.interval() {
+            Some(interval) => format!("every {interval}"),
+            None if calendar.chars().all(|c| c.is_ascii_alphabetic()) => calendar.to_string(),
+            None => format!("on {calendar}"),
+        }
+    }
+}
+
+/// The `[resources]` section of a job: how the generated systemd service runs it. The service is
+/// throttled by its cgroup, so the `nice`/`ionice` wrappers are not used there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Resources {
+    /// `Nice=`, from -20 (highest) to 19 (lowest priority).
+    #[serde(with = "unset")]
+    pub nice: Option<i32>,
+    /// `IOSchedulingClass=`: `idle`, `best-effort` or `realtime`.
+    #[serde(with = "unset")]
+    pub io_scheduling_class: Option<String>,
+    /// `CPUWeight=`, 1 to 10000 (systemd's default is 100).
+    #[serde(with = "unset")]
+    pub cpu_weight: Option<u32>,
+    /// `IOWeight=`, 1 to 10000 (systemd's default is 100).
+    #[serde(with = "unset")]
+    pub io_weight: Option<u32>,
+    /// `MemoryMax=`, e.g. `1G` or `20%`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_max: Option<String>,
+    /// `TimeoutStartSec=`: stop a run that takes longer than this (e.g. `2h`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Order timer runs after `network-online.target` and pull it in.
+    pub wait_for_network: bool,
+    /// Skip timer runs while on battery (`ConditionACPower=true`).
+    pub require_ac_power: bool,
+}
+
+impl Default for Resources {
+    fn default() -> Self {
+        Self {
+            nice: Some(19),
+            io_scheduling_class: Some("idle".into()),
+            cpu_weight: Some(20),
+            io_weight: Some(20),
+            memory_max: None,
+            timeout: None,
+            wait_for_network: true,
+            require_ac_power: false,
+        }
+    }
+}
+
+impl Resources {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Optional settings whose default is a value. A missing key means the default, so a cleared
+/// setting is written as `"unset"` to survive a reload.
+mod unset {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    enum Marker {
+        #[serde(rename = "unset")]
+        Unset,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value<T> {
+        Unset(Marker),
+        Set(T),
+    }
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_str("unset"),
         }
     }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        Ok(match Value::deserialize(deserializer)? {
+            Value::Unset(Marker::Unset) => None,
+            Value::Set(value) => Some(value),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,17 +250,65 @@ pub fn jobs_dir() -> Result<PathBuf> {
     Ok(dir)
 }
 
+/// Names of all jobs with a `<job>.toml` in the jobs dir, sorted.
+pub fn list_jobs() -> Result<Vec<String>> {
+    let dir = jobs_dir()?;
+    let mut jobs: Vec<String> = fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|ent| ent.ok())
+        .map(|ent| ent.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect();
+    jobs.sort();
+    Ok(jobs)
+}
+
+/// Directory for a job's per-run log files. Without an explicit `log_dir`, each job gets its own
+/// directory so runs of different jobs never share (or overwrite) log files.
+pub fn log_dir(cfg: &JobConfig) -> Result<PathBuf> {
+    if let Some(dir) = cfg.log_dir.as_deref().filter(|s| !s.trim().is_empty()) {
+        return Ok(expand_home(dir.trim()));
+    }
+    let home = std::env::var_os("HOME").context("HOME is not set")?;
+    Ok(PathBuf::from(home).join("logs/rclone-sync").join(&cfg.name))
+}
+
+/// Where jobs without `log_dir` wrote their logs before each job got its own directory. Shared
+/// by all jobs; `None` if the job sets `log_dir`.
+pub fn legacy_log_dir(cfg: &JobConfig) -> Option<PathBuf> {
+    if cfg.log_dir.as_deref().is_some_and(|s| !s.trim().is_empty()) {
+        return None;
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join("logs/rclone-sync"))
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(rest);
+    }
+    if let Some(rest) = path.strip_prefix("$HOME/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(rest);
+    }
+    PathBuf::from(path)
+}
+
 pub fn job_config_path(job: &str) -> Result<PathBuf> {
     Ok(jobs_dir()?.join(format!("{job}.toml")))
 }
 
 pub fn load_or_create_job(job: &str) -> Result<JobConfig> {
+    validate_job_name(job)?;
     let path = job_config_path(job)?;
     if path.exists() {
         let mut cfg = load_job_from_path(&path)?;
-        if cfg.name.trim().is_empty() {
-            cfg.name = job.to_string();
-        }
+        // The file name is the job's identity: lock, state and unit names are keyed on it, so a
+        // hand-edited `name` must not point them somewhere else.
+        cfg.name = job.to_string();
         // Migration: if no explicit pairs are configured, interpret each directory as a pair suffix.
         if cfg.pairs.is_empty() && cfg.directories.iter().any(|d| !d.trim().is_empty()) {
             cfg.pairs = cfg
@@ -119,6 +330,54 @@ pub fn load_or_create_job(job: &str) -> Result<JobConfig> {
     Ok(cfg)
 }
 
+/// Job names end up in file names and systemd unit names, so keep them to a safe set.
+pub fn validate_job_name(job: &str) -> Result<()> {
+    // Unit names escape anything else (see `systemd::escape_instance`); spaces are fine there.
+    let valid = !job.is_empty()
+        && !job.starts_with('.')
+        && job.trim() == job
+        && job
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ' '));
+    if !valid {
+        anyhow::bail!(
+            "Invalid job name '{job}': use letters, digits, spaces, '-', '_' or '.', not starting with '.'"
+        );
+    }
+    Ok(())
+}
+
+/// Config for a new single-pair job. `~` in the local folder is expanded and relative folders
+/// are made absolute, since rclone is not run from a shell.
+pub fn new_job(
+    job: &str,
+    local: &str,
+    remote: &str,
+    rclone_config_path: Option<&str>,
+) -> JobConfig {
+    let local = expand_home(local.trim());
+    let local = std::path::absolute(&local).unwrap_or(local);
+    JobConfig {
+        local_path: local.display().to_string(),
+        remote: remote.trim().to_string(),
+        rclone_config_path: rclone_config_path
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string),
+        ..JobConfig::empty(job)
+    }
+}
+
+/// Save a new job, refusing to overwrite an existing one.
+pub fn create_job(cfg: &JobConfig) -> Result<()> {
+    validate_job_name(&cfg.name)?;
+    let path = job_config_path(&cfg.name)?;
+    if path.exists() {
+        anyhow::bail!("Job '{}' already exists ({})", cfg.name, path.display());
+    }
+    save_job(cfg)
+}
+
 pub fn save_job(cfg: &JobConfig) -> Result<()> {
     let path = job_config_path(&cfg.name)?;
     let content = toml::to_string_pretty(cfg)?;
@@ -133,3 +392,28 @@ fn load_job_from_path(path: &Path) -> Result<JobConfig> {
         .with_context(|| format!("Failed to parse job config {}", path.display()))?;
     Ok(cfg)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleared_resource_limits_survive_save_and_load() {
+        let mut cfg = JobConfig::empty("docs");
+        cfg.resources.nice = None;
+        cfg.resources.io_scheduling_class = None;
+        cfg.resources.cpu_weight = Some(50);
+        cfg.resources.memory_max = Some("1G".into());
+
+        let path =
+            std::env::temp_dir().join(format!("sync-helper-test-{}.toml", std::process::id()));
+        fs::write(&path, toml::to_string_pretty(&cfg).unwrap()).unwrap();
+        let loaded = load_job_from_path(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(loaded.unwrap().resources, cfg.resources);
+
+        let defaults: JobConfig =
+            toml::from_str("name = \"docs\"\nlocal_path = \"\"\nremote = \"\"\n").unwrap();
+        assert_eq!(defaults.resources, Resources::default());
+    }
+}
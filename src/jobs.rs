@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::history;
+use crate::job_config::{self, JobConfig};
+use crate::runner;
+use crate::status;
+use crate::systemd::{self, SystemdUser};
+
+/// Delete a job's config along with its cached status, history, systemd units and lock file.
+/// The log directory is removed too unless `keep_logs` is set or the job uses a custom `log_dir`
+/// (which may be shared with other jobs).
+pub fn delete_job(job: &str, keep_logs: bool) -> Result<()> {
+    let cfg = load_existing(job)?;
+    ensure_not_running(&cfg)?;
+
+    let sd = SystemdUser::new()?;
+    if sd.units_installed(job) {
+        sd.remove_units(job)?;
+    }
+
+    remove_if_exists(&job_config::job_config_path(job)?)?;
+    status::remove_state(job)?;
+    let history_file = history::history_file_path(job)?;
+    remove_if_exists(&history_file)?;
+    remove_if_exists(&history::lock_file_path(&history_file))?;
+    if cfg.lock_file.is_none() {
+        remove_if_exists(&runner::lock_file_path(&cfg))?;
+    }
+    if !keep_logs && cfg.log_dir.is_none() {
+        let dir = job_config::log_dir(&cfg)?;
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Rename a job, moving its status, history, default log directory and systemd units along.
+/// An enabled timer is re-enabled under the new name.
+pub fn rename_job(old: &str, new: &str) -> Result<()> {
+    job_config::validate_job_name(new)?;
+    let cfg = load_existing(old)?;
+    let new_path = job_config::job_config_path(new)?;
+    if new_path.exists() {
+        anyhow::bail!("Job '{new}' already exists ({})", new_path.display());
+    }
+    ensure_not_running(&cfg)?;
+
+    let sd = SystemdUser::new()?;
+    let units_installed = sd.units_installed(old);
+    let timer_enabled = units_installed && sd.status(old).is_ok_and(|st| st.enabled);
+    if units_installed {
+        // Fail before touching anything if the new units can't be written.
+        systemd::validate_schedule(&cfg.schedule)?;
+        systemd::validate_resources(&cfg.resources)?;
+    }
+
+    let renamed = JobConfig {
+        name: new.to_string(),
+        ..cfg.clone()
+    };
+    job_config::save_job(&renamed)?;
+
+    // The old units stay until the new ones are in place, so a failed install leaves the job
+    // as it was.
+    if units_installed {
+        if let Err(err) = sd.install_units(new) {
+            let _ = sd.remove_units(new);
+            let _ = fs::remove_file(&new_path);
+            anyhow::bail!("Failed to install the units for job '{new}': {err:#}");
+        }
+        sd.remove_units(old)?;
+        if timer_enabled {
+            sd.enable_timer(new)?;
+        }
+    }
+
+    remove_if_exists(&job_config::job_config_path(old)?)?;
+    status::rename_state(old, new)?;
+    let old_history = history::history_file_path(old)?;
+    if old_history.exists() {
+        fs::rename(&old_history, history::history_file_path(new)?)
+            .with_context(|| format!("Failed to move {}", old_history.display()))?;
+    }
+    if cfg.lock_file.is_none() {
+        remove_if_exists(&runner::lock_file_path(&cfg))?;
+    }
+    if cfg.log_dir.is_none() {
+        let old_logs = job_config::log_dir(&cfg)?;
+        let new_logs = job_config::log_dir(&renamed)?;
+        if old_logs.exists() && !new_logs.exists() {
+            fs::rename(&old_logs, &new_logs)
+                .with_context(|| format!("Failed to move {}", old_logs.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Problems that would make a run of the job fail: config errors, a missing local folder, an
+/// unreachable remote or a schedule systemd would reject. Empty when the job looks fine.
+pub fn validate_job(job: &str) -> Vec<String> {
+    let cfg = match load_existing(job) {
+        Ok(cfg) => cfg,
+        Err(err) => return vec![format!("{err:#}")],
+    };
+    if let Err(err) = runner::validate_config(&cfg) {
+        return vec![err.to_string()];
+    }
+    let mut problems = crate::rclone::path_problems(&cfg);
+    // The default schedule is known to be valid; skip needing systemd-analyze for it.
+    if !cfg.schedule.is_default()
+        && let Err(err) = systemd::validate_schedule(&cfg.schedule)
+    {
+        problems.push(format!("Schedule: {err:#}"));
+    }
+    if !cfg.resources.is_default()
+        && let Err(err) = systemd::validate_resources(&cfg.resources)
+    {
+        problems.push(format!("Resources: {err:#}"));
+    }
+    problems
+}
+
+/// Load a job that must already exist (unlike `load_or_create_job`, which creates it).
+pub fn load_existing(job: &str) -> Result<JobConfig> {
+    let path = job_config::job_config_path(job)?;
+    if !path.exists() {
+        anyhow::bail!("Job '{job}' does not exist ({})", path.display());
+    }
+    job_config::load_or_create_job(job)
+}
+
+fn ensure_not_running(cfg: &JobConfig) -> Result<()> {
+    if let Some(info) = runner::detect_running(&runner::lock_file_path(cfg)) {
+        anyhow::bail!(
+            "Job '{}' is running (PID {}); cancel it or wait for it to finish",
+            cfg.name,
+            info.pid
+        );
+    }
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    if path.exists() {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
@@ -1,16 +1,28 @@
 mod applet;
+mod bisync_log;
 mod cli;
+mod config_editor;
+mod doctor;
+mod history;
 mod job_config;
+mod jobs;
 mod notify;
 mod open;
+mod rclone;
+mod remote_browser;
 mod runner;
 mod status;
 mod systemd;
+mod systemd_dbus;
 
 use clap::Parser;
+use serde::Serialize;
 use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use cli::{Cli, Commands, SystemdCommands};
+use cli::{Cli, Commands, JobsCommands, OutputFormat, SystemdCommands};
 
 fn main() -> cosmic::iced::Result {
     tracing_subscriber::fmt()
@@ -20,19 +32,79 @@ fn main() -> cosmic::iced::Result {
 
     // COSMIC panel may invoke applets with extra/unknown arguments; fall back to UI mode.
     let args: Vec<OsString> = std::env::args_os().collect();
-    let cli = Cli::try_parse_from(&args).unwrap_or(Cli { command: None });
+    let cli = Cli::try_parse_from(&args).unwrap_or(Cli {
+        command: None,
+        output: OutputFormat::Text,
+    });
+    let json = cli.output == OutputFormat::Json;
 
     match cli.command.unwrap_or(Commands::Ui) {
         Commands::Ui => cosmic::applet::run::<applet::AppletModel>(()),
-        Commands::Run { job } => {
-            if let Err(err) = run_once(&job) {
+        Commands::Run {
+            job,
+            all,
+            concurrency,
+            pair,
+            resync,
+            dry_run,
+            trigger,
+            queue,
+        } => {
+            let opts = runner::RunOptions {
+                dry_run,
+                trigger: trigger.for_service_start(),
+                queue,
+                resync,
+                pair: None,
+            };
+            let res = if all {
+                run_all(&opts, concurrency, json)
+            } else {
+                run_selected(&job, pair.as_deref(), opts, json).map(|_| 0)
+            };
+            match res {
+                Ok(0) => {}
+                Ok(code) => std::process::exit(code),
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+            }
+            Ok(())
+        }
+        Commands::Cancel { job } => {
+            if let Err(err) = cancel(&job, json) {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Commands::History {
+            job,
+            json: history_json,
+            limit,
+        } => {
+            if let Err(err) = print_history(&job, json || history_json, limit) {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Commands::Doctor { job } => {
+            if !print_doctor(job, json) {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Commands::Jobs { command } => {
+            if let Err(err) = handle_jobs(command, json) {
                 eprintln!("{err}");
                 std::process::exit(1);
             }
             Ok(())
         }
         Commands::Systemd { command } => {
-            if let Err(err) = handle_systemd(command) {
+            if let Err(err) = handle_systemd(command, json) {
                 eprintln!("{err}");
                 std::process::exit(1);
             }
@@ -41,43 +113,676 @@ fn main() -> cosmic::iced::Result {
     }
 }
 
-fn run_once(job: &str) -> anyhow::Result<()> {
+/// A finished run as printed by `--output json`.
+#[derive(Serialize)]
+struct RunReport {
+    job: String,
+    #[serde(flatten)]
+    result: status::ScriptResult,
+    changed_count: Option<u32>,
+    /// The job's status after the run, as the applet shows it.
+    state: status::SyncState,
+}
+
+/// A job `run --all` could not start, listed with the `RunReport`s of the others.
+#[derive(Serialize)]
+struct RunFailure<'a> {
+    job: &'a str,
+    /// Always `error`, next to the outcomes of `RunReport`.
+    outcome: &'static str,
+    error: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum RunAllEntry<'a> {
+    Ran(&'a RunReport),
+    Failed(RunFailure<'a>),
+}
+
+#[derive(Serialize)]
+struct CancelReport<'a> {
+    job: &'a str,
+    cancel_requested: bool,
+}
+
+/// One job of `jobs list`; `config` is missing (and `error` says why) if it doesn't load.
+#[derive(Serialize)]
+struct JobListEntry {
+    job: String,
+    config: Option<job_config::JobConfig>,
+    error: Option<String>,
+    state: status::SyncState,
+}
+
+/// `jobs show`: the job's config and every file it uses.
+#[derive(Serialize)]
+struct JobDetails {
+    config_file: PathBuf,
+    log_dir: PathBuf,
+    lock_file: PathBuf,
+    status_file: PathBuf,
+    history_file: PathBuf,
+    units_installed: bool,
+    pairs: Vec<PairPaths>,
+    config: job_config::JobConfig,
+}
+
+#[derive(Serialize)]
+struct PairPaths {
+    local: String,
+    remote: String,
+}
+
+/// `jobs create` without `--local`/`--remote`: the remotes to pick from.
+#[derive(Serialize)]
+struct RemoteList {
+    remotes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CreateReport {
+    config: job_config::JobConfig,
+    /// The initial `--resync` run, if one was requested.
+    resync: Option<RunReport>,
+}
+
+#[derive(Serialize)]
+struct DeleteReport {
+    deleted: String,
+}
+
+#[derive(Serialize)]
+struct ValidateReport<'a> {
+    job: &'a str,
+    problems: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ScheduleReport<'a> {
+    job: &'a str,
+    schedule: job_config::Schedule,
+    /// Empty for interval schedules, which have no fixed times.
+    next_elapses: Vec<String>,
+}
+
+fn print_json<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn run_selected(
+    job: &str,
+    pair: Option<&str>,
+    mut opts: runner::RunOptions,
+    json: bool,
+) -> anyhow::Result<()> {
+    if let Some(selector) = pair {
+        let cfg = job_config::load_or_create_job(job)?;
+        opts.pair = Some(runner::find_pair(&cfg, selector)?);
+    }
+    let report = run_and_report(job, &opts, json)?;
+    if json {
+        print_json(&report)?;
+    }
+    ensure_succeeded(job, &report.result)
+}
+
+fn ensure_succeeded(job: &str, result: &status::ScriptResult) -> anyhow::Result<()> {
+    if result.outcome == runner::RunOutcome::Failed {
+        if result.dry_run {
+            anyhow::bail!("Dry run for job {} failed (exit {})", job, result.exit_code);
+        }
+        anyhow::bail!("Job {} failed (exit {})", job, result.exit_code);
+    }
+    Ok(())
+}
+
+/// Run every job, `concurrency` at a time, then print a summary table. Returns the exit code:
+/// 0 if nothing failed, otherwise the highest exit code among failed jobs (1 for jobs that
+/// could not be started).
+fn run_all(opts: &runner::RunOptions, concurrency: usize, json: bool) -> anyhow::Result<i32> {
+    let jobs = job_config::list_jobs()?;
+    if jobs.is_empty() {
+        if json {
+            print_json(&Vec::<RunReport>::new())?;
+        } else {
+            println!("No jobs configured");
+        }
+        return Ok(0);
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<Result<RunReport, String>>>> =
+        jobs.iter().map(|_| Mutex::new(None)).collect();
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, jobs.len()) {
+            scope.spawn(|| {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(job) = jobs.get(idx) else {
+                        break;
+                    };
+                    let res = run_and_report(job, opts, json).map_err(|e| e.to_string());
+                    *results[idx].lock().unwrap() = Some(res);
+                }
+            });
+        }
+    });
+    let results: Vec<_> = results
+        .into_iter()
+        .map(|res| res.into_inner().unwrap())
+        .collect();
+
+    let mut exit_code = 0;
+    for res in results.iter().flatten() {
+        match res {
+            Ok(report) if report.result.outcome == runner::RunOutcome::Failed => {
+                exit_code = exit_code.max(report.result.exit_code.max(1));
+            }
+            Ok(_) => {}
+            Err(_) => exit_code = exit_code.max(1),
+        }
+    }
+
+    if json {
+        let reports: Vec<RunAllEntry> = jobs
+            .iter()
+            .zip(&results)
+            .filter_map(|(job, res)| match res.as_ref()? {
+                Ok(report) => Some(RunAllEntry::Ran(report)),
+                Err(err) => Some(RunAllEntry::Failed(RunFailure {
+                    job,
+                    outcome: "error",
+                    error: err,
+                })),
+            })
+            .collect();
+        print_json(&reports)?;
+        return Ok(exit_code);
+    }
+
+    println!(
+        "\n{:<20} {:<9} {:>4} {:>9} {:>7}",
+        "JOB", "OUTCOME", "EXIT", "DURATION", "CHANGES"
+    );
+    for (job, res) in jobs.iter().zip(results) {
+        match res {
+            Some(Ok(RunReport { result, .. })) => {
+                println!(
+                    "{job:<20} {:<9} {:>4} {:>9} {:>7}",
+                    result.outcome.label(),
+                    result.exit_code,
+                    result
+                        .duration_secs
+                        .map(|s| format!("{s}s"))
+                        .unwrap_or_else(|| "-".into()),
+                    result
+                        .changed_count()
+                        .map(|c| c.to_string())
+                        .unwrap_or_else(|| "-".into()),
+                );
+            }
+            Some(Err(err)) => {
+                println!(
+                    "{job:<20} {:<9} {:>4} {:>9} {:>7}  {err}",
+                    "error", "-", "-", "-"
+                );
+            }
+            None => {}
+        }
+    }
+    Ok(exit_code)
+}
+
+/// Run a job and report it like a single `run` does (preview, errors, notifications), without
+/// turning a failed sync into an error. With `json` the text output is left to the caller.
+fn run_and_report(job: &str, opts: &runner::RunOptions, json: bool) -> anyhow::Result<RunReport> {
     let cfg = job_config::load_or_create_job(job)?;
 
     let mut store = status::StatusStore::load(job)?;
-    let result = store.run_sync(&cfg)?;
+    let result = store.run_sync(&cfg, opts)?;
     let state = store.state();
+    let report = RunReport {
+        job: job.to_string(),
+        changed_count: result.changed_count(),
+        result,
+        state,
+    };
+    let (result, state) = (&report.result, &report.state);
+
+    if result.outcome == runner::RunOutcome::Skipped {
+        if !json {
+            println!("{}", result.stderr);
+        }
+        return Ok(report);
+    }
+
+    if result.dry_run {
+        if !json {
+            print_preview(job, &result.pairs);
+            if let Some(log) = &result.log_file {
+                println!("Log: {log}");
+            }
+        }
+        return Ok(report);
+    }
+
+    if result.outcome == runner::RunOutcome::Cancelled {
+        if !json {
+            eprintln!("Job {job} was cancelled");
+        }
+        return Ok(report);
+    }
 
     // Notifications for non-interactive runs (errors always; successes only if there were changes).
     if result.exit_code != 0 {
+        for (idx, pair) in result.pairs.iter().enumerate() {
+            if let Some(err) = &pair.error
+                && !json
+            {
+                eprintln!(
+                    "pair {}/{} {} <-> {} failed (exit {}): {err}",
+                    idx + 1,
+                    result.pairs.len(),
+                    pair.local,
+                    pair.remote,
+                    pair.exit_code
+                );
+            }
+        }
         let body = state
             .last_error
             .clone()
             .unwrap_or_else(|| format!("Job {job} failed (exit {})", result.exit_code));
         let _ = notify::notify("Rclone Sync Failed", &body, true);
-        anyhow::bail!("Job {} failed (exit {})", job, result.exit_code);
     } else if let Some(changed) = state.last_changed_count {
         if changed > 0 {
             let body = format!("Job {job}: synced {changed} item(s)");
             let _ = notify::notify("Rclone Sync Completed", &body, false);
         }
     }
+    Ok(report)
+}
+
+fn print_preview(job: &str, pairs: &[runner::PairResult]) {
+    println!("Dry run for job {job} (no changes were made)");
+    for (idx, pair) in pairs.iter().enumerate() {
+        println!(
+            "\npair {}/{}: {} <-> {} (exit={})",
+            idx + 1,
+            pairs.len(),
+            pair.local,
+            pair.remote,
+            pair.exit_code
+        );
+        for (side, summary) in [("Path1", &pair.path1), ("Path2", &pair.path2)] {
+            if let Some(s) = summary {
+                println!(
+                    "  {side}: {} changes ({} new, {} newer, {} older, {} deleted)",
+                    s.total, s.new, s.newer, s.older, s.deleted
+                );
+            }
+        }
+        if pair.changes.is_empty() {
+            println!("  No changes found");
+        }
+        for change in &pair.changes {
+            println!(
+                "  {:?} {:<9} {}",
+                change.side,
+                format!("{:?}", change.kind).to_lowercase(),
+                change.path
+            );
+        }
+    }
+}
+
+fn cancel(job: &str, json: bool) -> anyhow::Result<()> {
+    let cfg = job_config::load_or_create_job(job)?;
+    runner::cancel_job(&cfg)?;
+    if json {
+        print_json(&CancelReport {
+            job,
+            cancel_requested: true,
+        })?;
+    } else {
+        println!("Cancellation requested for job {job}");
+    }
     Ok(())
 }
 
-fn handle_systemd(cmd: SystemdCommands) -> anyhow::Result<()> {
-    let sd = systemd::SystemdUser::new()?;
+fn print_history(job: &str, json: bool, limit: usize) -> anyhow::Result<()> {
+    let records = history::load(job, limit)?;
+    if json {
+        return print_json(&records);
+    }
+    if records.is_empty() {
+        println!("No runs recorded for job {job}");
+        return Ok(());
+    }
+    for record in &records {
+        let started = record
+            .started_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S");
+        let duration = record
+            .duration_secs
+            .map(|s| format!("{s}s"))
+            .unwrap_or_else(|| "-".into());
+        let changes = record
+            .changed_count
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".into());
+        print!(
+            "{started}  {:<7} {:<9} exit={:<3} {:>6}  changes={changes}",
+            record.trigger.label(),
+            record.outcome.label(),
+            record.exit_code,
+            duration,
+        );
+        if let Some(err) = &record.error {
+            print!("  {err}");
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Print the doctor checks; returns false if any failed.
+fn print_doctor(job: Option<String>, json: bool) -> bool {
+    let checks = doctor::run(&job.into_iter().collect::<Vec<_>>());
+    let failed = checks.iter().any(|c| c.status == doctor::CheckStatus::Fail);
+    if json {
+        if let Err(err) = print_json(&checks) {
+            eprintln!("{err}");
+            return false;
+        }
+        return !failed;
+    }
+    for check in &checks {
+        println!(
+            "[{}] {}: {}",
+            check.status.label(),
+            check.name,
+            check.detail
+        );
+        if let Some(hint) = &check.hint {
+            println!("       {hint}");
+        }
+    }
+    !failed
+}
+
+fn handle_jobs(cmd: JobsCommands, json: bool) -> anyhow::Result<()> {
     match cmd {
-        SystemdCommands::Install { job } => sd.install_units(&job)?,
-        SystemdCommands::Enable { job } => sd.enable_timer(&job)?,
-        SystemdCommands::Disable { job } => sd.disable_timer(&job)?,
-        SystemdCommands::Status { job } => {
-            let st = sd.status(&job)?;
+        JobsCommands::List => {
+            let names = job_config::list_jobs()?;
+            if json {
+                let mut list = Vec::new();
+                for job in names {
+                    let state = status::StatusStore::load(&job)?.state();
+                    let (config, error) = match jobs::load_existing(&job) {
+                        Ok(cfg) => (Some(cfg), None),
+                        Err(err) => (None, Some(format!("{err:#}"))),
+                    };
+                    list.push(JobListEntry {
+                        job,
+                        config,
+                        error,
+                        state,
+                    });
+                }
+                return print_json(&list);
+            }
+            if names.is_empty() {
+                println!("No jobs configured");
+            }
+            for job in names {
+                let state = status::StatusStore::load(&job)?.state();
+                let summary = match jobs::load_existing(&job) {
+                    Ok(cfg) if cfg.pairs.is_empty() => {
+                        format!("{} <-> {}", cfg.local_path, cfg.remote)
+                    }
+                    Ok(cfg) => format!("{} pairs", cfg.pairs.len()),
+                    Err(err) => format!("invalid config: {err:#}"),
+                };
+                let last = match (state.last_outcome, state.last_run) {
+                    (Some(outcome), Some(at)) => format!(
+                        "{} {}",
+                        outcome.label(),
+                        at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                    ),
+                    _ => "never run".into(),
+                };
+                println!("{job:<20} {last:<27} {summary}");
+            }
+        }
+        JobsCommands::Show { job } => {
+            let cfg = jobs::load_existing(&job)?;
+            if json {
+                return print_json(&JobDetails {
+                    config_file: job_config::job_config_path(&job)?,
+                    log_dir: job_config::log_dir(&cfg)?,
+                    lock_file: runner::lock_file_path(&cfg),
+                    status_file: status::state_file_path(&job)?,
+                    history_file: history::history_file_path(&job)?,
+                    units_installed: systemd::SystemdUser::new()?.units_installed(&job),
+                    pairs: runner::resolved_pairs(&cfg)
+                        .into_iter()
+                        .map(|(local, remote)| PairPaths { local, remote })
+                        .collect(),
+                    config: cfg,
+                });
+            }
+            println!("# {}", job_config::job_config_path(&job)?.display());
+            print!("{}", toml::to_string_pretty(&cfg)?);
+            println!();
+            for (idx, (local, remote)) in runner::resolved_pairs(&cfg).iter().enumerate() {
+                println!("pair {}: {local} <-> {remote}", idx + 1);
+            }
+            println!("log dir: {}", job_config::log_dir(&cfg)?.display());
+            println!("lock file: {}", runner::lock_file_path(&cfg).display());
+            println!("status file: {}", status::state_file_path(&job)?.display());
+            println!("history: {}", history::history_file_path(&job)?.display());
             println!(
-                "{} enabled={} active={} next={:?}",
-                st.unit, st.enabled, st.active, st.next_elapse
+                "systemd units: {}",
+                if systemd::SystemdUser::new()?.units_installed(&job) {
+                    "installed"
+                } else {
+                    "not installed"
+                }
             );
         }
+        JobsCommands::Create {
+            job,
+            local,
+            remote,
+            rclone_config,
+            resync,
+        } => {
+            let (Some(local), Some(remote)) = (local, remote) else {
+                let remotes = rclone::list_remotes(rclone_config.as_deref())?;
+                if json {
+                    print_json(&RemoteList { remotes })?;
+                } else if remotes.is_empty() {
+                    println!("No rclone remotes configured; run `rclone config` first.");
+                } else {
+                    println!("Configured remotes:");
+                    for remote in remotes {
+                        println!("  {remote}");
+                    }
+                }
+                anyhow::bail!(
+                    "Pass --local <folder> and --remote <remote:path> to create job {job}"
+                );
+            };
+            let cfg = job_config::new_job(&job, &local, &remote, rclone_config.as_deref());
+            job_config::validate_job_name(&job)?;
+            runner::validate_config(&cfg)?;
+            rclone::check_job_paths(&cfg)?;
+            job_config::create_job(&cfg)?;
+            if !json {
+                println!("Created job {job}: {} <-> {}", cfg.local_path, cfg.remote);
+            }
+            let report = if resync {
+                let opts = runner::RunOptions {
+                    resync: true,
+                    ..Default::default()
+                };
+                Some(run_and_report(&job, &opts, json)?)
+            } else {
+                None
+            };
+            let resync_ok = report
+                .as_ref()
+                .map(|report| ensure_succeeded(&job, &report.result));
+            if json {
+                print_json(&CreateReport {
+                    config: cfg,
+                    resync: report,
+                })?;
+            }
+            resync_ok.transpose()?;
+        }
+        JobsCommands::Delete { job, keep_logs } => {
+            jobs::delete_job(&job, keep_logs)?;
+            if json {
+                print_json(&DeleteReport { deleted: job })?;
+            } else {
+                println!("Deleted job {job}");
+            }
+        }
+        JobsCommands::Rename { job, to } => {
+            jobs::rename_job(&job, &to)?;
+            if json {
+                print_json(&jobs::load_existing(&to)?)?;
+            } else {
+                println!("Renamed job {job} to {to}");
+            }
+        }
+        JobsCommands::Validate { job } => {
+            let names = match job {
+                Some(job) => vec![job],
+                None => job_config::list_jobs()?,
+            };
+            let mut failed = 0;
+            let mut results = Vec::new();
+            for job in &names {
+                let problems = jobs::validate_job(job);
+                if !problems.is_empty() {
+                    failed += 1;
+                }
+                if json {
+                    results.push(ValidateReport { job, problems });
+                } else if problems.is_empty() {
+                    println!("{job}: ok");
+                } else {
+                    for problem in problems {
+                        println!("{job}: {problem}");
+                    }
+                }
+            }
+            if json {
+                print_json(&results)?;
+            }
+            if failed > 0 {
+                anyhow::bail!("{failed} of {} job(s) have problems", names.len());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs a systemd subcommand. With `json`, every subcommand prints the timer status it leaves
+/// behind.
+fn handle_systemd(cmd: SystemdCommands, json: bool) -> anyhow::Result<()> {
+    let sd = systemd::SystemdUser::new()?;
+    let job = match cmd {
+        SystemdCommands::Install { job } => {
+            sd.install_units(&job)?;
+            job
+        }
+        SystemdCommands::Enable { job } => {
+            sd.enable_timer(&job)?;
+            job
+        }
+        SystemdCommands::Disable { job } => {
+            sd.disable_timer(&job)?;
+            job
+        }
+        SystemdCommands::Status { job } => {
+            if !json {
+                let st = sd.status(&job)?;
+                println!(
+                    "{} installed={} enabled={} active={} next={}",
+                    st.unit,
+                    st.installed,
+                    st.enabled,
+                    st.active,
+                    st.next_elapse.as_deref().unwrap_or("-")
+                );
+                if let Some(result) = st.last_result() {
+                    println!(
+                        "last run: {} ({})",
+                        result,
+                        st.last_trigger
+                            .as_deref()
+                            .unwrap_or("not triggered by the timer")
+                    );
+                }
+                for line in &st.journal_tail {
+                    println!("  {line}");
+                }
+                if let Some(err) = &st.manager_error {
+                    println!("systemd user manager not reachable, showing unit files only: {err}");
+                }
+            }
+            job
+        }
+        SystemdCommands::Uninstall { job } => {
+            sd.remove_units(&job)?;
+            if !json {
+                println!("Removed the systemd units for job {job}");
+            }
+            job
+        }
+        SystemdCommands::Repair { job } => {
+            let repaired = sd.repair_units(&job)?;
+            if !json {
+                if repaired.is_empty() {
+                    println!("Units for job {job} are up to date");
+                } else {
+                    println!("Rewrote {}", repaired.join(", "));
+                }
+            }
+            job
+        }
+        SystemdCommands::Schedule { job, count } => return print_schedule(&job, count, json),
+    };
+    if json {
+        print_json(&sd.status(&job)?)?;
+    }
+    Ok(())
+}
+
+fn print_schedule(job: &str, count: usize, json: bool) -> anyhow::Result<()> {
+    let schedule = jobs::load_existing(job)?.schedule;
+    systemd::validate_schedule(&schedule)?;
+    let next = match schedule.interval() {
+        Some(_) => vec![],
+        None => systemd::calendar_next_elapses(&schedule.on_calendar, count)?,
+    };
+    if json {
+        return print_json(&ScheduleReport {
+            job,
+            schedule,
+            next_elapses: next,
+        });
+    }
+    println!("Job {job} runs {}", schedule.describe());
+    for time in next {
+        println!("  {time}");
     }
     Ok(())
 }
@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::job_config::{self, JobConfig};
+
+/// One entry of `rclone lsjson` output.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RemoteEntry {
+    pub path: String,
+    pub name: String,
+    /// Directories report `-1` on most backends.
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub is_dir: bool,
+    #[serde(default)]
+    pub mod_time: Option<DateTime<Utc>>,
+}
+
+/// Total size of a folder, from `rclone size --json`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct FolderSize {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+/// The installed rclone's version, e.g. `(1, 68, 2)` from `rclone v1.68.2`.
+pub fn version() -> Result<(u32, u32, u32)> {
+    let out = rclone(&["version"], None)?;
+    parse_version(&out).with_context(|| format!("Unrecognised rclone version output: {out}"))
+}
+
+fn parse_version(out: &str) -> Option<(u32, u32, u32)> {
+    let version = out.lines().next()?.split_whitespace().nth(1)?;
+    let mut parts = version
+        .trim_start_matches('v')
+        .split(['.', '-'])
+        .map(|p| p.parse::<u32>());
+    Some((
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next().and_then(|p| p.ok()).unwrap_or(0),
+    ))
+}
+
+/// Path of the rclone config file in use (`rclone config file`).
+pub fn config_file(config_path: Option<&str>) -> Result<PathBuf> {
+    let out = rclone(&["config", "file"], config_path)?;
+    out.lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty() && !l.ends_with(':'))
+        .map(PathBuf::from)
+        .context("rclone config file printed no path")
+}
+
+/// Name of the configured remote a path refers to (`gdrive:` for `gdrive:Photos`), or `None` for
+/// local paths and on-the-fly `:backend:` remotes.
+pub fn remote_name(path: &str) -> Option<String> {
+    let (name, _) = path.split_once(':')?;
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(format!("{name}:"))
+}
+
+/// Remotes configured in rclone's config, e.g. `["gdrive:", "nas:"]`.
+pub fn list_remotes(config_path: Option<&str>) -> Result<Vec<String>> {
+    let out = rclone(&["listremotes"], config_path)?;
+    Ok(out
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Look up a single remote path with `rclone lsjson --stat`.
+pub fn stat(remote: &str, config_path: Option<&str>) -> Result<RemoteEntry> {
+    let out = rclone(&["lsjson", "--stat", remote], config_path)?;
+    serde_json::from_str(&out)
+        .with_context(|| format!("Failed to parse rclone lsjson output for {remote}"))
+}
+
+/// Subfolders of a remote path (`rclone lsjson --dirs-only`), sorted by name.
+/// Works for any backend, including plain local paths.
+pub fn list_dirs(remote: &str, config_path: Option<&str>) -> Result<Vec<RemoteEntry>> {
+    let out = rclone(&["lsjson", "--dirs-only", remote], config_path)?;
+    parse_entries(&out)
+        .with_context(|| format!("Failed to parse rclone lsjson output for {remote}"))
+}
+
+/// Count and total size of the files under a remote path. Walks the whole tree, so this can
+/// take a while on large folders.
+pub fn folder_size(remote: &str, config_path: Option<&str>) -> Result<FolderSize> {
+    let out = rclone(&["size", "--json", remote], config_path)?;
+    serde_json::from_str(&out)
+        .with_context(|| format!("Failed to parse rclone size output for {remote}"))
+}
+
+fn parse_entries(out: &str) -> Result<Vec<RemoteEntry>> {
+    let mut entries: Vec<RemoteEntry> = serde_json::from_str(out)?;
+    entries.sort_by_key(|e| e.name.to_lowercase());
+    Ok(entries)
+}
+
+/// Check that both sides of every pair exist and are directories before a job is saved.
+pub fn check_job_paths(cfg: &JobConfig) -> Result<()> {
+    let problems = path_problems(cfg);
+    if !problems.is_empty() {
+        anyhow::bail!("{}", problems.join("\n"));
+    }
+    Ok(())
+}
+
+/// Missing local folders and unreachable (or non-folder) remotes across the job's pairs.
+pub fn path_problems(cfg: &JobConfig) -> Vec<String> {
+    let config_path = cfg.rclone_config_path.as_deref();
+    let mut problems = Vec::new();
+    for (local, remote) in crate::runner::resolved_pairs(cfg) {
+        let local_path = job_config::expand_home(&local);
+        if !Path::new(&local_path).is_dir() {
+            problems.push(format!(
+                "Local folder {} does not exist",
+                local_path.display()
+            ));
+        }
+        match stat(&remote, config_path) {
+            Ok(entry) if !entry.is_dir => {
+                problems.push(format!("Remote {remote} is a file, not a folder"))
+            }
+            Ok(_) => {}
+            Err(err) => problems.push(format!("Remote {remote} is not reachable: {err:#}")),
+        }
+    }
+    problems
+}
+
+fn rclone(args: &[&str], config_path: Option<&str>) -> Result<String> {
+    let mut cmd = Command::new("rclone");
+    cmd.args(args);
+    if let Some(path) = config_path.map(str::trim).filter(|p| !p.is_empty()) {
+        cmd.arg("--config").arg(job_config::expand_home(path));
+    }
+    let output = cmd
+        .output()
+        .with_context(|| format!("Failed to run rclone {}", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "rclone {} failed (code {:?}): {}",
+            args.join(" "),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_and_remote_names() {
+        let out = "rclone v1.68.2\n- os/version: debian 12 (64 bit)\n- go/version: go1.23.3\n";
+        assert_eq!(parse_version(out), Some((1, 68, 2)));
+        assert_eq!(
+            parse_version("rclone v1.69.0-beta.8300\n"),
+            Some((1, 69, 0))
+        );
+        assert_eq!(
+            remote_name("gdrive:Photos/2024").as_deref(),
+            Some("gdrive:")
+        );
+        assert_eq!(remote_name(":local:/tmp"), None);
+        assert_eq!(remote_name("/home/me/a:b"), None);
+    }
+
+    #[test]
+    fn parses_lsjson_dirs_sorted_by_name() {
+        let out = r#"[
+{"Path":"photos","Name":"photos","Size":-1,"MimeType":"inode/directory","ModTime":"2024-05-01T10:20:30.123456789+02:00","IsDir":true},
+{"Path":"Documents","Name":"Documents","Size":-1,"MimeType":"inode/directory","ModTime":"2024-04-01T08:00:00Z","IsDir":true,"ID":"abc"}
+]"#;
+        let entries = parse_entries(out).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Documents", "photos"]);
+        assert!(entries.iter().all(|e| e.is_dir && e.size == -1));
+        assert_eq!(
+            entries[1].mod_time.unwrap().to_rfc3339(),
+            "2024-05-01T08:20:30.123456789+00:00"
+        );
+    }
+}
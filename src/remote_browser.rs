@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::rclone::{FolderSize, RemoteEntry};
+
+/// Where the folder picked in the remote browser goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowseTarget {
+    NewJob,
+    EditorRemote,
+    EditorPair(usize),
+}
+
+/// State of the remote folder browser: a remote, a folder within it and that folder's subfolders.
+#[derive(Debug, Clone)]
+pub struct RemoteBrowser {
+    pub target: BrowseTarget,
+    pub config_path: Option<String>,
+    /// Remotes from `rclone listremotes`, plus the starting remote if it isn't one of them
+    /// (e.g. a local path or an on-the-fly `:backend:` remote).
+    pub remotes: Vec<String>,
+    /// `remotes` as shown in the picker; the empty remote is the local filesystem.
+    pub remote_labels: Vec<String>,
+    pub remote_idx: Option<usize>,
+    /// Folder within the remote, without a trailing `/`.
+    pub path: String,
+    pub entries: Vec<RemoteEntry>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Folder sizes measured on request, by full rclone path; `None` while measuring.
+    /// `lsjson` reports `-1` for folders, so they have no size until then.
+    pub sizes: HashMap<String, Option<Result<FolderSize, String>>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum BrowseMessage {
+    RemotesLoaded(Result<Vec<String>, String>),
+    /// Listing of the given remote path finished.
+    Listed(String, Result<Vec<RemoteEntry>, String>),
+    Remote(usize),
+    Enter(String),
+    /// Measure the size of the named subfolder.
+    MeasureSize(String),
+    SizeMeasured(String, Result<FolderSize, String>),
+    Up,
+    Select,
+    Cancel,
+}
+
+impl RemoteBrowser {
+    /// Start browsing at `start` (e.g. `gdrive:Photos`); empty starts at the first remote.
+    pub fn new(target: BrowseTarget, start: &str, config_path: Option<String>) -> Self {
+        let (remote, path) = split_remote(start.trim());
+        let mut browser = Self {
+            target,
+            config_path: config_path.filter(|p| !p.trim().is_empty()),
+            remotes: vec![],
+            remote_labels: vec![],
+            remote_idx: None,
+            path,
+            entries: vec![],
+            loading: true,
+            error: None,
+            sizes: HashMap::new(),
+        };
+        if !start.trim().is_empty() {
+            browser.set_remotes(vec![remote]);
+        }
+        browser
+    }
+
+    /// Merge in the configured remotes, keeping the current selection.
+    pub fn set_remotes(&mut self, remotes: Vec<String>) {
+        let current = self.remote_idx.and_then(|i| self.remotes.get(i).cloned());
+        let mut all = remotes;
+        if let Some(current) = &current
+            && !all.contains(current)
+        {
+            all.insert(0, current.clone());
+        }
+        self.remote_idx = match &current {
+            Some(current) => all.iter().position(|r| r == current),
+            None => (!all.is_empty()).then_some(0),
+        };
+        self.remote_labels = all
+            .iter()
+            .map(|r| {
+                if r.is_empty() {
+                    "Local disk".to_string()
+                } else {
+                    r.clone()
+                }
+            })
+            .collect();
+        self.remotes = all;
+    }
+
+    pub fn remote(&self) -> Option<&str> {
+        self.remotes.get(self.remote_idx?).map(String::as_str)
+    }
+
+    /// Full rclone path of the current folder, e.g. `gdrive:Photos/2024`.
+    pub fn current(&self) -> Option<String> {
+        Some(format!("{}{}", self.remote()?, self.path))
+    }
+
+    pub fn select_remote(&mut self, idx: usize) {
+        if idx < self.remotes.len() {
+            self.remote_idx = Some(idx);
+            self.path.clear();
+        }
+    }
+
+    pub fn enter(&mut self, name: &str) {
+        self.path = self.child_path(name);
+    }
+
+    /// Full rclone path of the subfolder `name` of the current folder.
+    pub fn entry_path(&self, name: &str) -> Option<String> {
+        Some(format!("{}{}", self.remote()?, self.child_path(name)))
+    }
+
+    fn child_path(&self, name: &str) -> String {
+        if self.path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{name}", self.path.trim_end_matches('/'))
+        }
+    }
+
+    pub fn can_go_up(&self) -> bool {
+        !self.path.trim_matches('/').is_empty()
+    }
+
+    pub fn up(&mut self) {
+        let trimmed = self.path.trim_end_matches('/');
+        self.path = match trimmed.rsplit_once('/') {
+            // Parent of `/home` in a local path is the root.
+            Some(("", _)) => "/".into(),
+            Some((parent, _)) => parent.into(),
+            None => String::new(),
+        };
+    }
+}
+
+/// Split `gdrive:Photos/2024` into `("gdrive:", "Photos/2024")`. Paths without a remote
+/// (local folders) have an empty remote.
+pub fn split_remote(s: &str) -> (String, String) {
+    match s.find(':') {
+        Some(idx) if !s[..idx].contains('/') => (
+            s[..=idx].to_string(),
+            s[idx + 1..].trim_end_matches('/').to_string(),
+        ),
+        _ => (String::new(), s.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigates_remote_and_local_paths() {
+        assert_eq!(
+            split_remote("gdrive:Photos/2024/"),
+            ("gdrive:".into(), "Photos/2024".into())
+        );
+        assert_eq!(
+            split_remote("/home/me/a:b"),
+            (String::new(), "/home/me/a:b".into())
+        );
+
+        let mut browser = RemoteBrowser::new(BrowseTarget::NewJob, "gdrive:Photos", None);
+        browser.set_remotes(vec!["alias:".into(), "gdrive:".into()]);
+        assert_eq!(browser.remote(), Some("gdrive:"));
+        assert_eq!(
+            browser.entry_path("2024").as_deref(),
+            Some("gdrive:Photos/2024")
+        );
+        browser.enter("2024");
+        assert_eq!(browser.current().as_deref(), Some("gdrive:Photos/2024"));
+        browser.up();
+        browser.up();
+        assert_eq!(browser.current().as_deref(), Some("gdrive:"));
+        assert!(!browser.can_go_up());
+
+        let mut local = RemoteBrowser::new(BrowseTarget::EditorRemote, "/home/me", None);
+        local.set_remotes(vec!["gdrive:".into()]);
+        assert_eq!(local.remotes, vec!["", "gdrive:"]);
+        local.up();
+        assert_eq!(local.current().as_deref(), Some("/home"));
+        local.up();
+        assert_eq!(local.current().as_deref(), Some("/"));
+        assert!(!local.can_go_up());
+    }
+}
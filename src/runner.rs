@@ -1,52 +1,249 @@
 use std::fs;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::{Duration, SystemTime};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::job_config::{JobConfig, SyncPair};
+use crate::bisync_log::{self, BisyncEvent, ChangeSummary, PlannedChange, RcloneStats};
+use crate::job_config::{self, JobConfig, SyncPair, expand_home};
+
+/// Directory (under `$XDG_RUNTIME_DIR`) holding the per-job lock files.
+const LOCK_DIR_NAME: &str = "rclone-sync-helper";
+/// How often rclone emits a stats line while a pair is syncing.
+const STATS_INTERVAL: &str = "2s";
+/// Set (to the unit name) when a run was started by one of our systemd units.
+pub const UNIT_ENV: &str = "RCLONESYNC_HELPER_UNIT";
 
 #[derive(Debug)]
 pub struct RunResult {
     pub timestamp: DateTime<Utc>,
+    pub trigger: Trigger,
     pub exit_code: i32,
+    pub outcome: RunOutcome,
     pub stdout: String,
     pub stderr: String,
     pub log_file: Option<String>,
     pub duration_secs: Option<u64>,
+    pub dry_run: bool,
+    /// One entry per pair that was started, in configuration order.
+    pub pairs: Vec<PairResult>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Pass `--dry-run` to every pair and collect the planned changes instead of applying them.
+    pub dry_run: bool,
+    /// What started the run; recorded in the lock so others can tell who owns it.
+    pub trigger: Trigger,
+    /// What to do when another run of the job holds the lock.
+    pub queue: QueueMode,
+    /// Pass `--resync` on the first attempt, e.g. for the initial run of a new job.
+    pub resync: bool,
+    /// Run only this pair (an index into `resolved_pairs`) instead of all of them.
+    pub pair: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum QueueMode {
+    /// Don't run; report the run as skipped.
+    #[default]
+    Skip,
+    /// Wait for the running sync to finish, then run.
+    Wait,
+    /// Run once after the running sync finishes; further requests made meanwhile are skipped.
+    Coalesce,
+}
+
+/// Where a run came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// `rclone_sync_helper run` from a terminal or script.
+    #[default]
+    Manual,
+    /// "Sync now" / "Preview changes" in the applet.
+    Applet,
+    /// The systemd --user timer.
+    Timer,
+}
+
+impl Trigger {
+    pub fn label(self) -> &'static str {
+        match self {
+            Trigger::Manual => "manual",
+            Trigger::Applet => "applet",
+            Trigger::Timer => "timer",
+        }
+    }
+
+    /// The service unit passes `--trigger timer`, but the applet's "Sync now" starts it too.
+    /// systemd (249+) only sets `$TRIGGER_UNIT` when the timer activated the service; older
+    /// managers never set it, so their runs stay timer runs.
+    pub fn for_service_start(self) -> Self {
+        let in_unit = std::env::var_os("INVOCATION_ID").is_some();
+        if self == Trigger::Timer
+            && in_unit
+            && std::env::var_os("TRIGGER_UNIT").is_none()
+            && crate::systemd_dbus::manager_version().is_ok_and(|v| v >= 249)
+        {
+            Trigger::Applet
+        } else {
+            self
+        }
+    }
+}
+
+/// How a run ended. Kept separate from the exit code so a cancelled run is not reported as a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Success,
+    Failed,
+    Cancelled,
+    /// Another run held the job's lock, so nothing was synced.
+    Skipped,
+}
+
+impl RunOutcome {
+    pub fn label(self) -> &'static str {
+        match self {
+            RunOutcome::Success => "success",
+            RunOutcome::Failed => "failed",
+            RunOutcome::Cancelled => "cancelled",
+            RunOutcome::Skipped => "skipped",
+        }
+    }
+}
+
+/// Outcome of one `SyncPair` within a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairResult {
+    /// Resolved local path (after applying `local_path`).
+    pub local: String,
+    /// Resolved remote path (after applying `remote`).
+    pub remote: String,
+    pub attempts: Vec<PairAttempt>,
+    /// Exit code of the last attempt.
+    pub exit_code: i32,
+    pub duration_secs: u64,
+    pub path1: Option<ChangeSummary>,
+    pub path2: Option<ChangeSummary>,
+    pub error: Option<String>,
+    /// Per-file decisions; only collected for dry runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<PlannedChange>,
+    /// Final rclone accounting stats of the last attempt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<RcloneStats>,
+}
+
+impl PairResult {
+    /// Total number of changes detected on both sides, if bisync reported them.
+    pub fn changed_count(&self) -> Option<u32> {
+        match (self.path1, self.path2) {
+            (None, None) => None,
+            (a, b) => Some(
+                a.map(|s| s.total)
+                    .unwrap_or(0)
+                    .saturating_add(b.map(|s| s.total).unwrap_or(0)),
+            ),
+        }
+    }
+}
+
+/// Live progress of a running sync, published next to the lock file for the applet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncProgress {
+    /// 1-based index of the pair being synced.
+    pub pair: usize,
+    pub pair_count: usize,
+    pub local: String,
+    pub remote: String,
+    /// Latest stats of the current attempt; `None` until rclone reports the first one.
+    pub stats: Option<RcloneStats>,
+    pub updated_at: DateTime<Utc>,
 }
 
-pub fn run_job(cfg: &JobConfig) -> Result<RunResult> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairAttempt {
+    pub kind: AttemptKind,
+    pub exit_code: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptKind {
+    Normal,
+    RetryAfterLockCleanup,
+    ResyncRecovery,
+}
+
+impl AttemptKind {
+    fn log_label(self) -> &'static str {
+        match self {
+            AttemptKind::Normal => "attempt=normal",
+            AttemptKind::RetryAfterLockCleanup => "attempt=retry_after_lock_cleanup",
+            AttemptKind::ResyncRecovery => "attempt=resync_recovery",
+        }
+    }
+}
+
+pub fn run_job(cfg: &JobConfig, opts: &RunOptions) -> Result<RunResult> {
     let timestamp = Utc::now();
 
     validate_config(cfg)?;
 
-    if cfg.clean_bisync_locks {
+    let mut pairs = resolved_pairs(cfg);
+    if let Some(idx) = opts.pair {
+        if idx >= pairs.len() {
+            anyhow::bail!("Job '{}' has no pair {}", cfg.name, idx + 1);
+        }
+        pairs = vec![pairs.swap_remove(idx)];
+    }
+
+    // A preview must not touch the filesystem, stale bisync locks included.
+    if cfg.clean_bisync_locks && !opts.dry_run {
         let _ = clean_bisync_locks();
     }
 
-    let lock_path = cfg
-        .lock_file
-        .as_deref()
-        .filter(|s| !s.trim().is_empty())
-        .unwrap_or("/tmp/rclone-sync.lock");
-    let _lock_guard = match LockGuard::acquire(lock_path) {
-        Ok(g) => Some(g),
-        Err(LockError::AlreadyRunning(pid)) => {
-            return Ok(RunResult {
-                timestamp,
-                exit_code: 0,
-                stdout: String::new(),
-                stderr: format!("Sync already running (PID: {pid}). Skipping this run."),
-                log_file: None,
-                duration_secs: None,
-            });
-        }
-        Err(LockError::Other(err)) => return Err(err),
+    let skipped = |owner: LockInfo, queued: bool| RunResult {
+        timestamp,
+        trigger: opts.trigger,
+        exit_code: 0,
+        outcome: RunOutcome::Skipped,
+        stdout: String::new(),
+        stderr: format!(
+            "Sync already running (job: {}, PID: {}, trigger: {}){}. Skipping this run.",
+            owner.job,
+            owner.pid,
+            owner.trigger.label(),
+            if queued {
+                " and another run is queued"
+            } else {
+                ""
+            }
+        ),
+        log_file: None,
+        duration_secs: None,
+        dry_run: opts.dry_run,
+        pairs: Vec::new(),
     };
+    let lock_guard =
+        match LockGuard::acquire(&lock_file_path(cfg), &cfg.name, opts.trigger, opts.queue) {
+            Ok(g) => g,
+            Err(LockError::AlreadyRunning(owner)) => return Ok(skipped(owner, false)),
+            Err(LockError::AlreadyQueued(owner)) => return Ok(skipped(owner, true)),
+            Err(LockError::Other(err)) => return Err(err),
+        };
+    // Time spent waiting in the queue is not part of the run.
+    let timestamp = Utc::now();
 
     let (mut log_file, log_file_path) = create_log_file(cfg, timestamp)?;
     writeln!(log_file, "=== rclone bisync run started ===")?;
@@ -54,6 +251,15 @@ pub fn run_job(cfg: &JobConfig) -> Result<RunResult> {
     writeln!(log_file, "local_base={}", cfg.local_path)?;
     writeln!(log_file, "remote_base={}", cfg.remote)?;
     writeln!(log_file, "timestamp={}", timestamp.to_rfc3339())?;
+    if opts.dry_run {
+        writeln!(log_file, "mode=dry-run")?;
+    }
+    if opts.resync {
+        writeln!(log_file, "mode=resync")?;
+    }
+    if let Some(idx) = opts.pair {
+        writeln!(log_file, "only_pair={}", idx + 1)?;
+    }
     if !cfg.pairs.is_empty() {
         writeln!(
             log_file,
@@ -65,83 +271,86 @@ pub fn run_job(cfg: &JobConfig) -> Result<RunResult> {
         )?;
     }
 
-    let pairs: Vec<SyncPair> = if cfg.pairs.is_empty() {
-        vec![SyncPair {
-            local: cfg.local_path.clone(),
-            remote: cfg.remote.clone(),
-        }]
-    } else {
-        cfg.pairs.clone()
-    };
-
     let mut combined_stdout = String::new();
     let mut combined_stderr = String::new();
     let mut final_exit = 0;
+    let mut cancelled = false;
+    let mut pair_results = Vec::new();
 
-    for (idx, pair) in pairs.iter().enumerate() {
-        let (local, remote) = resolve_pair_paths(cfg, pair);
-        let label = format!("pair {}/{}: {} <-> {}", idx + 1, pairs.len(), local, remote);
+    // Dry runs need INFO-level output to see the per-file decisions.
+    let mut base_extra: Vec<&str> = Vec::new();
+    if opts.dry_run {
+        base_extra.push("--dry-run");
+        if !has_verbosity_flag(&cfg.extra_args) {
+            base_extra.push("-v");
+        }
+    }
+    if opts.resync {
+        base_extra.push("--resync");
+    }
+
+    let pair_count = pairs.len();
+    for (idx, (local, remote)) in pairs.into_iter().enumerate() {
+        if lock_guard.cancel_requested() {
+            cancelled = true;
+            break;
+        }
+
+        let label = format!("pair {}/{pair_count}: {local} <-> {remote}", idx + 1);
         writeln!(log_file, "\n=== {label} ===")?;
 
-        let attempt = |extra: &[&str]| -> Result<(i32, String, String)> {
-            let mut cmd = build_command(cfg, &local, &remote, extra)?;
-            let output = cmd.output().with_context(|| {
-                format!(
-                    "Failed to execute rclone bisync for job {} ({} <-> {})",
-                    cfg.name, local, remote
-                )
-            })?;
-            let code = output.status.code().unwrap_or(-1);
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            Ok((code, stdout, stderr))
+        let pair_started = Instant::now();
+        let mut progress = SyncProgress {
+            pair: idx + 1,
+            pair_count,
+            local: local.clone(),
+            remote: remote.clone(),
+            stats: None,
+            updated_at: Utc::now(),
+        };
+        let mut attempts = Vec::new();
+        let mut attempt = |log_file: &mut fs::File, kind: AttemptKind, extra: &[&str]| {
+            let args: Vec<&str> = base_extra.iter().chain(extra).copied().collect();
+            let res = run_attempt(
+                cfg,
+                &args,
+                kind.log_label(),
+                log_file,
+                &lock_guard,
+                &mut progress,
+            )?;
+            attempts.push(PairAttempt {
+                kind,
+                exit_code: res.exit_code,
+            });
+            Ok::<_, anyhow::Error>(res)
         };
 
         // First attempt
-        let (mut exit_code, mut last_stdout, mut last_stderr) = attempt(&[])?;
-        write_log_chunk(
-            &mut log_file,
-            "attempt=normal",
-            exit_code,
-            &last_stdout,
-            &last_stderr,
-        )?;
-        let _ = log_file.flush();
+        let mut last = attempt(&mut log_file, AttemptKind::Normal, &[])?;
+        cancelled = lock_guard.cancel_requested();
 
         // Retry after lock cleanup (requested).
-        if exit_code != 0 {
-            if let Some(lock_path) = detect_prior_lock_file(&last_stdout, &last_stderr) {
-                if remove_stale_lock_file(&lock_path).unwrap_or(false) {
-                    let (c, out, err) = attempt(&[])?;
-                    exit_code = c;
-                    last_stdout = out;
-                    last_stderr = err;
-                    write_log_chunk(
-                        &mut log_file,
-                        "attempt=retry_after_lock_cleanup",
-                        exit_code,
-                        &last_stdout,
-                        &last_stderr,
-                    )?;
-                }
-            }
+        if last.exit_code != 0
+            && !cancelled
+            && !opts.dry_run
+            && let Some(lock_path) = bisync_log::prior_lock_file(&last.events)
+            && remove_stale_lock_file(lock_path).unwrap_or(false)
+        {
+            last = attempt(&mut log_file, AttemptKind::RetryAfterLockCleanup, &[])?;
+            cancelled = lock_guard.cancel_requested();
         }
 
         // Recovery: if bisync indicates a resync is required, optionally retry with --resync.
-        if exit_code != 0 && needs_resync(&last_stdout, &last_stderr) {
+        // A run that already passed --resync has nothing left to recover with.
+        if last.exit_code != 0
+            && !cancelled
+            && !opts.resync
+            && bisync_log::resync_required(&last.events)
+        {
             if cfg.auto_resync {
-                let (c, out, err) = attempt(&["--resync"])?;
-                exit_code = c;
-                last_stdout = out;
-                last_stderr = err;
-                write_log_chunk(
-                    &mut log_file,
-                    "attempt=resync_recovery",
-                    exit_code,
-                    &last_stdout,
-                    &last_stderr,
-                )?;
-                let _ = log_file.flush();
+                last = attempt(&mut log_file, AttemptKind::ResyncRecovery, &["--resync"])?;
+                cancelled = lock_guard.cancel_requested();
             } else {
                 writeln!(
                     log_file,
@@ -151,39 +360,76 @@ pub fn run_job(cfg: &JobConfig) -> Result<RunResult> {
             }
         }
 
-        if !combined_stdout.is_empty() && !last_stdout.is_empty() {
+        if !combined_stdout.is_empty() && !last.stdout.is_empty() {
             combined_stdout.push('\n');
         }
-        combined_stdout.push_str(&last_stdout);
+        combined_stdout.push_str(&last.stdout);
 
-        if !combined_stderr.is_empty() && !last_stderr.is_empty() {
+        if !combined_stderr.is_empty() && !last.stderr.is_empty() {
             combined_stderr.push('\n');
         }
-        combined_stderr.push_str(&last_stderr);
+        combined_stderr.push_str(&last.stderr);
 
-        if exit_code != 0 {
-            final_exit = exit_code;
+        if last.exit_code != 0 {
+            final_exit = last.exit_code;
+        }
+
+        let (path1, path2) = bisync_log::change_summaries(&last.events);
+        pair_results.push(PairResult {
+            error: (last.exit_code != 0 && !cancelled).then(|| last.error_summary()),
+            changes: if opts.dry_run {
+                bisync_log::planned_changes(&last.events)
+            } else {
+                Vec::new()
+            },
+            local,
+            remote,
+            attempts,
+            exit_code: last.exit_code,
+            duration_secs: pair_started.elapsed().as_secs(),
+            path1,
+            path2,
+            stats: bisync_log::last_json_stats(&last.events).cloned(),
+        });
+
+        cancelled = cancelled || lock_guard.cancel_requested();
+        if cancelled {
+            break;
         }
     }
 
-    writeln!(
-        log_file,
-        "=== rclone bisync run finished (exit={}) ===",
-        final_exit
-    )?;
+    let outcome = if cancelled {
+        writeln!(log_file, "\n=== rclone bisync run cancelled ===")?;
+        RunOutcome::Cancelled
+    } else {
+        writeln!(
+            log_file,
+            "=== rclone bisync run finished (exit={}) ===",
+            final_exit
+        )?;
+        if final_exit == 0 {
+            RunOutcome::Success
+        } else {
+            RunOutcome::Failed
+        }
+    };
     let duration_secs = (Utc::now() - timestamp).num_seconds().max(0) as u64;
 
     Ok(RunResult {
         timestamp,
+        trigger: opts.trigger,
         exit_code: final_exit,
+        outcome,
         stdout: combined_stdout,
         stderr: combined_stderr,
         log_file: Some(log_file_path.display().to_string()),
         duration_secs: Some(duration_secs),
+        dry_run: opts.dry_run,
+        pairs: pair_results,
     })
 }
 
-fn validate_config(cfg: &JobConfig) -> Result<()> {
+pub fn validate_config(cfg: &JobConfig) -> Result<()> {
     let base_local_ok = !cfg.local_path.trim().is_empty();
     let base_remote_ok = !cfg.remote.trim().is_empty();
 
@@ -264,8 +510,28 @@ fn build_command(
     args.extend(cfg.extra_args.iter().cloned());
     args.extend(extra_args.iter().map(|s| s.to_string()));
 
-    // Prefer running with low priority if possible.
-    if cfg.use_nice_ionice && cmd_exists("nice") && cmd_exists("ionice") {
+    // JSON log lines carry the stats as an object, so progress and counts don't depend on
+    // rclone's text formatting.
+    if !has_flag(&args, "--use-json-log") {
+        args.push("--use-json-log".into());
+    }
+    if !has_flag(&args, "--stats") {
+        args.push("--stats".into());
+        args.push(STATS_INTERVAL.into());
+    }
+    if !has_flag(&args, "--stats-one-line") {
+        args.push("--stats-one-line".into());
+    }
+    // Stats are logged at INFO, below a normal run's NOTICE level.
+    if !has_flag(&args, "--stats-log-level") && !has_verbosity_flag(&args) {
+        args.push("--stats-log-level".into());
+        args.push("NOTICE".into());
+    }
+
+    // Prefer running with low priority if possible. Our systemd units set their own priority
+    // and limits instead.
+    let in_unit = std::env::var_os(UNIT_ENV).is_some();
+    if cfg.use_nice_ionice && !in_unit && cmd_exists("nice") && cmd_exists("ionice") {
         let mut cmd = Command::new("nice");
         cmd.arg("-n").arg("19");
         cmd.arg("ionice").arg("-c").arg("3");
@@ -279,6 +545,44 @@ fn build_command(
     }
 }
 
+/// Resolved `(local, remote)` paths for every pair the job syncs.
+pub fn resolved_pairs(cfg: &JobConfig) -> Vec<(String, String)> {
+    if cfg.pairs.is_empty() {
+        return vec![(cfg.local_path.clone(), cfg.remote.clone())];
+    }
+    cfg.pairs
+        .iter()
+        .map(|pair| resolve_pair_paths(cfg, pair))
+        .collect()
+}
+
+/// Find a pair by 1-based index or by local path (as configured or resolved).
+pub fn find_pair(cfg: &JobConfig, selector: &str) -> Result<usize> {
+    let pairs = resolved_pairs(cfg);
+    let selector = selector.trim();
+    if let Ok(n) = selector.parse::<usize>() {
+        if n == 0 || n > pairs.len() {
+            anyhow::bail!(
+                "Job '{}' has {} pair(s); --pair must be between 1 and {}",
+                cfg.name,
+                pairs.len(),
+                pairs.len()
+            );
+        }
+        return Ok(n - 1);
+    }
+    let wanted = selector.trim_end_matches('/');
+    (0..pairs.len())
+        .find(|&idx| {
+            let configured = cfg
+                .pairs
+                .get(idx)
+                .is_some_and(|p| p.local.trim().trim_end_matches('/') == wanted);
+            configured || pairs[idx].0.trim_end_matches('/') == wanted
+        })
+        .with_context(|| format!("Job '{}' has no pair with local path {selector}", cfg.name))
+}
+
 fn resolve_pair_paths(cfg: &JobConfig, pair: &SyncPair) -> (String, String) {
     let local = pair.local.trim();
     let remote = pair.remote.trim();
@@ -309,7 +613,7 @@ fn resolve_pair_paths(cfg: &JobConfig, pair: &SyncPair) -> (String, String) {
     (resolved_local, resolved_remote)
 }
 
-fn cmd_exists(name: &str) -> bool {
+pub fn cmd_exists(name: &str) -> bool {
     Command::new("which")
         .arg(name)
         .output()
@@ -318,12 +622,7 @@ fn cmd_exists(name: &str) -> bool {
 }
 
 fn create_log_file(cfg: &JobConfig, timestamp: DateTime<Utc>) -> Result<(fs::File, PathBuf)> {
-    let dir = if let Some(dir) = cfg.log_dir.as_deref().filter(|s| !s.trim().is_empty()) {
-        expand_home(dir)
-    } else {
-        let home = std::env::var_os("HOME").context("HOME is not set")?;
-        PathBuf::from(home).join("logs/rclone-sync")
-    };
+    let dir = job_config::log_dir(cfg)?;
     fs::create_dir_all(&dir)?;
 
     let name = format!("sync_{}.log", timestamp.format("%Y%m%d_%H%M%S"));
@@ -333,59 +632,163 @@ fn create_log_file(cfg: &JobConfig, timestamp: DateTime<Utc>) -> Result<(fs::Fil
     Ok((file, path))
 }
 
-fn expand_home(path: &str) -> PathBuf {
-    if let Some(rest) = path.strip_prefix("~/") {
-        if let Some(home) = std::env::var_os("HOME") {
-            return PathBuf::from(home).join(rest);
-        }
+/// Run one bisync invocation for the pair described by `progress`, appending its output to the
+/// run log line by line as it arrives.
+///
+/// stdout and stderr are drained on separate threads so neither pipe can fill up and stall rclone;
+/// the full output is still returned for post-run analysis.
+fn run_attempt(
+    cfg: &JobConfig,
+    extra_args: &[&str],
+    label: &str,
+    log_file: &mut fs::File,
+    lock: &LockGuard,
+    progress: &mut SyncProgress,
+) -> Result<AttemptOutput> {
+    let (local, remote) = (progress.local.clone(), progress.remote.clone());
+    let mut cmd = build_command(cfg, &local, &remote, extra_args)?;
+    // Own process group so a cancel can signal rclone together with any nice/ionice wrapper.
+    cmd.process_group(0)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().with_context(|| {
+        format!(
+            "Failed to execute rclone bisync for job {} ({} <-> {})",
+            cfg.name, local, remote
+        )
+    })?;
+
+    lock.record_child(Some(child.id()));
+    progress.stats = None;
+    progress.updated_at = Utc::now();
+    lock.record_progress(progress);
+    writeln!(log_file, "\n--- {label} ---")?;
+    let _ = log_file.flush();
+
+    let (tx, rx) = mpsc::channel::<(OutputStream, String)>();
+    let mut readers = Vec::new();
+    if let Some(out) = child.stdout.take() {
+        readers.push(spawn_line_reader(out, OutputStream::Stdout, tx.clone()));
+    }
+    if let Some(err) = child.stderr.take() {
+        readers.push(spawn_line_reader(err, OutputStream::Stderr, tx.clone()));
     }
-    if let Some(rest) = path.strip_prefix("$HOME/") {
-        if let Some(home) = std::env::var_os("HOME") {
-            return PathBuf::from(home).join(rest);
+    drop(tx);
+
+    // The log and the returned text get rclone's usual text layout; the raw JSON lines are
+    // kept only for parsing.
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    let mut raw = String::new();
+    for (stream, line) in rx {
+        if let Some(stats) = bisync_log::json_stats(&line) {
+            progress.stats = Some(stats);
+            progress.updated_at = Utc::now();
+            lock.record_progress(progress);
         }
+        let text = bisync_log::render_line(&line);
+        writeln!(log_file, "{text}")?;
+        let _ = log_file.flush();
+        let buf = match stream {
+            OutputStream::Stdout => &mut stdout,
+            OutputStream::Stderr => &mut stderr,
+        };
+        buf.push_str(&text);
+        buf.push('\n');
+        raw.push_str(&line);
+        raw.push('\n');
+    }
+    for reader in readers {
+        let _ = reader.join();
     }
-    PathBuf::from(path)
+
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for rclone bisync ({local} <-> {remote})"))?;
+    lock.record_child(None);
+    let exit_code = status.code().unwrap_or(-1);
+
+    writeln!(log_file, "--- {label} finished (exit={exit_code}) ---")?;
+    let _ = log_file.flush();
+
+    let events = bisync_log::parse(&raw);
+    Ok(AttemptOutput {
+        exit_code,
+        stdout,
+        stderr,
+        events,
+    })
 }
 
-fn write_log_chunk(
-    file: &mut fs::File,
-    label: &str,
+struct AttemptOutput {
     exit_code: i32,
-    stdout: &str,
-    stderr: &str,
-) -> Result<()> {
-    writeln!(file, "\n--- {label} (exit={exit_code}) ---")?;
-    if !stdout.trim().is_empty() {
-        writeln!(file, "STDOUT:\n{stdout}")?;
-    }
-    if !stderr.trim().is_empty() {
-        writeln!(file, "STDERR:\n{stderr}")?;
+    stdout: String,
+    stderr: String,
+    events: Vec<BisyncEvent>,
+}
+
+impl AttemptOutput {
+    /// Pick the most useful line explaining a failure: the last ERROR/CRITICAL event, else the
+    /// last line of stderr, else the exit code.
+    fn error_summary(&self) -> String {
+        bisync_log::last_error(&self.events)
+            .or_else(|| self.stderr.lines().map(str::trim).rfind(|l| !l.is_empty()))
+            .map(|l| l.to_string())
+            .unwrap_or_else(|| format!("Exited with code {}", self.exit_code))
     }
-    Ok(())
 }
 
-fn needs_resync(stdout: &str, stderr: &str) -> bool {
-    let combined = format!("{stdout}\n{stderr}").to_lowercase();
-    combined.contains("cannot find prior path1 or path2 listings")
-        || combined.contains("must run --resync")
-        || combined.contains("bisync aborted")
-}
-
-fn detect_prior_lock_file(stdout: &str, stderr: &str) -> Option<String> {
-    for line in stdout.lines().chain(stderr.lines()) {
-        if let Some(rest) = line.split("prior lock file found:").nth(1) {
-            let candidate = rest
-                .trim()
-                .split_whitespace()
-                .next()
-                .unwrap_or("")
-                .to_string();
-            if !candidate.is_empty() {
-                return Some(candidate);
+#[derive(Debug, Clone, Copy)]
+enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+fn spawn_line_reader<R: Read + Send + 'static>(
+    pipe: R,
+    stream: OutputStream,
+    tx: mpsc::Sender<(OutputStream, String)>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf)
+                        .trim_end_matches(['\r', '\n'])
+                        .to_string();
+                    if tx.send((stream, line)).is_err() {
+                        break;
+                    }
+                }
             }
         }
-    }
-    None
+    })
+}
+
+/// Whether `flag` is present, either on its own or as `flag=value`.
+fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|a| {
+        let a = a.trim();
+        a == flag
+            || a.strip_prefix(flag)
+                .is_some_and(|rest| rest.starts_with('='))
+    })
+}
+
+fn has_verbosity_flag(args: &[String]) -> bool {
+    args.iter().any(|a| {
+        let a = a.trim();
+        a == "--verbose"
+            || a.starts_with("--verbose=")
+            || a.starts_with("--log-level")
+            || (a.starts_with("-v") && a[1..].chars().all(|c| c == 'v'))
+    })
 }
 
 fn remove_stale_lock_file(path: &str) -> Result<bool> {
@@ -394,10 +797,7 @@ fn remove_stale_lock_file(path: &str) -> Result<bool> {
         return Ok(false);
     }
 
-    let pid = fs::read_to_string(&path)
-        .ok()
-        .and_then(|s| s.lines().next().map(|l| l.trim().to_string()))
-        .and_then(|l| l.parse::<u32>().ok());
+    let pid = bisync_lock_pid(&path);
 
     // If it contains a PID and that process is not alive, it's stale.
     if let Some(pid) = pid {
@@ -417,9 +817,44 @@ fn remove_stale_lock_file(path: &str) -> Result<bool> {
     Ok(false)
 }
 
-fn clean_bisync_locks() -> Result<()> {
+fn bisync_lock_dir() -> Result<PathBuf> {
     let home = std::env::var_os("HOME").context("HOME is not set")?;
-    let dir = PathBuf::from(home).join(".cache/rclone/bisync");
+    Ok(PathBuf::from(home).join(".cache/rclone/bisync"))
+}
+
+/// PID recorded on the first line of a bisync `.lck` file, if any.
+fn bisync_lock_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.lines().next().map(|l| l.trim().to_string()))
+        .and_then(|l| l.parse::<u32>().ok())
+}
+
+/// Bisync `.lck` files whose process is gone (or, without a PID, older than an hour).
+pub fn stale_bisync_locks() -> Result<Vec<PathBuf>> {
+    let dir = bisync_lock_dir()?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut stale = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let p = entry?.path();
+        if p.extension().and_then(|s| s.to_str()) != Some("lck") {
+            continue;
+        }
+        let is_stale = match bisync_lock_pid(&p) {
+            Some(pid) => !pid_alive(pid),
+            None => file_older_than(&p, Duration::from_secs(60 * 60)),
+        };
+        if is_stale {
+            stale.push(p);
+        }
+    }
+    Ok(stale)
+}
+
+fn clean_bisync_locks() -> Result<()> {
+    let dir = bisync_lock_dir()?;
     if !dir.is_dir() {
         return Ok(());
     }
@@ -442,11 +877,7 @@ fn clean_bisync_locks() -> Result<()> {
         if p.extension().and_then(|s| s.to_str()) != Some("lck") {
             continue;
         }
-        let pid = fs::read_to_string(&p)
-            .ok()
-            .and_then(|s| s.lines().next().map(|l| l.trim().to_string()))
-            .and_then(|l| l.parse::<u32>().ok());
-        if let Some(pid) = pid {
+        if let Some(pid) = bisync_lock_pid(&p) {
             if !pid_alive(pid) {
                 let _ = fs::remove_file(p);
             }
@@ -483,72 +914,400 @@ fn file_older_than(path: &Path, age: Duration) -> bool {
     elapsed > age
 }
 
+/// Resolve the lock file used by a job: `lock_file` if configured, otherwise
+/// `$XDG_RUNTIME_DIR/rclone-sync-helper/<job>.lock`.
+pub fn lock_file_path(cfg: &JobConfig) -> PathBuf {
+    if let Some(path) = cfg.lock_file.as_deref().filter(|s| !s.trim().is_empty()) {
+        return expand_home(path.trim());
+    }
+    let dir = match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join(LOCK_DIR_NAME),
+        None => {
+            let user = std::env::var("USER").unwrap_or_else(|_| "user".into());
+            std::env::temp_dir().join(format!("{LOCK_DIR_NAME}-{user}"))
+        }
+    };
+    dir.join(format!("{}.lock", cfg.name))
+}
+
+/// Request cancellation of the sync currently holding the job's lock.
+///
+/// The owning runner is asked to stop via a marker file next to the lock, and the rclone process
+/// group it is waiting on (if any) is terminated so the wrapper chain exits as well.
+pub fn cancel_job(cfg: &JobConfig) -> Result<()> {
+    let path = lock_file_path(cfg);
+    let Some(owner) = lock_owner(&path) else {
+        anyhow::bail!("No sync is running for job '{}'", cfg.name);
+    };
+
+    fs::write(cancel_marker_path(&path), format!("{}\n", owner.pid))
+        .with_context(|| format!("Failed to request cancellation of job '{}'", cfg.name))?;
+
+    if let Some(pgid) = owner.child_pgid.filter(|p| pid_alive(*p)) {
+        let status = Command::new("kill")
+            .args(["-TERM", "--", &format!("-{pgid}")])
+            .status()
+            .context("Failed to run kill")?;
+        if !status.success() {
+            anyhow::bail!("Failed to terminate rclone process group {pgid}");
+        }
+    }
+    Ok(())
+}
+
+/// Contents of a job lock file while a run holds it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub job: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub trigger: Trigger,
+    /// Process group of the rclone invocation currently running, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub child_pgid: Option<u32>,
+}
+
+/// The owner of `path`, if a run currently holds its lock.
+///
+/// The file outlives the run that wrote it, so its content only counts while the `flock` is held.
+fn lock_owner(path: &Path) -> Option<LockInfo> {
+    let file = fs::File::open(path).ok()?;
+    match file.try_lock_shared() {
+        Ok(()) => None,
+        Err(fs::TryLockError::WouldBlock) => read_lock_info(&file),
+        Err(fs::TryLockError::Error(_)) => None,
+    }
+}
+
+fn read_lock_info(mut file: &fs::File) -> Option<LockInfo> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut content).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn cancel_marker_path(lock_path: &Path) -> PathBuf {
+    let mut name = lock_path.as_os_str().to_owned();
+    name.push(".cancel");
+    PathBuf::from(name)
+}
+
+fn progress_path(lock_path: &Path) -> PathBuf {
+    let mut name = lock_path.as_os_str().to_owned();
+    name.push(".progress");
+    PathBuf::from(name)
+}
+
+/// Progress of the sync currently holding `lock_file`, if it has published any.
+pub fn read_progress(lock_file: &Path) -> Option<SyncProgress> {
+    let content = fs::read(progress_path(lock_file)).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+/// Holds the job's `flock` for the duration of a run. The lock file itself is left in place:
+/// removing a locked file would let the next run lock a fresh inode while this one still runs.
 #[derive(Debug)]
 struct LockGuard {
     path: PathBuf,
+    file: fs::File,
+    info: LockInfo,
 }
 
 #[derive(Debug)]
 enum LockError {
-    AlreadyRunning(u32),
+    AlreadyRunning(LockInfo),
+    /// Another run is already waiting for the lock (`QueueMode::Coalesce`).
+    AlreadyQueued(LockInfo),
     Other(anyhow::Error),
 }
 
-impl LockGuard {
-    fn acquire(path: &str) -> std::result::Result<Self, LockError> {
-        let path = expand_home(path);
-        if let Ok(existing) = fs::read_to_string(&path) {
-            if let Ok(pid) = existing.trim().parse::<u32>() {
-                if pid_alive(pid) {
-                    return Err(LockError::AlreadyRunning(pid));
+fn try_lock_exclusive(file: &fs::File, path: &Path, job: &str) -> Result<(), LockError> {
+    // A reader probing the lock (`lock_owner`) can hold it for a moment, so the lock is retried
+    // briefly before giving up. The recorded pid can't tell a reader apart from a run: it may
+    // belong to a run that was killed before it could clear the file.
+    let mut retries = 10;
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(()),
+            Err(fs::TryLockError::WouldBlock) => {
+                if retries == 0 {
+                    return Err(LockError::AlreadyRunning(read_lock_info(file).unwrap_or(
+                        LockInfo {
+                            job: job.to_string(),
+                            pid: 0,
+                            started_at: Utc::now(),
+                            trigger: Trigger::Manual,
+                            child_pgid: None,
+                        },
+                    )));
                 }
+                retries -= 1;
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(fs::TryLockError::Error(err)) => {
+                return Err(LockError::Other(
+                    anyhow::Error::new(err).context(format!("Failed to lock {}", path.display())),
+                ));
+            }
+        }
+    }
+}
+
+fn wait_for_lock(file: &fs::File, path: &Path) -> Result<(), LockError> {
+    file.lock()
+        .with_context(|| format!("Failed to wait for lock {}", path.display()))
+        .map_err(LockError::Other)
+}
+
+fn queued_marker_path(lock_path: &Path) -> PathBuf {
+    let mut name = lock_path.as_os_str().to_owned();
+    name.push(".queued");
+    PathBuf::from(name)
+}
+
+/// Marks that a run is waiting for the lock; at most one run per job waits at a time.
+struct QueuedMarker {
+    path: PathBuf,
+}
+
+impl QueuedMarker {
+    /// Returns `None` if a live run is already queued.
+    fn create(lock_path: &Path) -> Option<Self> {
+        let path = queued_marker_path(lock_path);
+        for _ in 0..2 {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Some(Self { path });
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let waiting = fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|c| c.trim().parse::<u32>().ok());
+                    // An empty marker may still be being written; only a dead waiter is stale.
+                    if waiting.is_none_or(pid_alive) {
+                        return None;
+                    }
+                    let _ = fs::remove_file(&path);
+                }
+                Err(_) => return None,
             }
-            let _ = fs::remove_file(&path);
         }
+        None
+    }
+}
 
+impl Drop for QueuedMarker {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl LockGuard {
+    fn acquire(
+        path: &Path,
+        job: &str,
+        trigger: Trigger,
+        queue: QueueMode,
+    ) -> std::result::Result<Self, LockError> {
         if let Some(parent) = path.parent() {
             let _ = fs::create_dir_all(parent);
         }
 
-        let mut file = fs::File::create(&path).map_err(|e| LockError::Other(e.into()))?;
-        let pid = std::process::id();
-        writeln!(file, "{pid}").map_err(|e| LockError::Other(e.into()))?;
-        Ok(Self { path })
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))
+            .map_err(LockError::Other)?;
+
+        match try_lock_exclusive(&file, path, job) {
+            Ok(()) => {}
+            Err(LockError::AlreadyRunning(owner)) => match queue {
+                QueueMode::Skip => return Err(LockError::AlreadyRunning(owner)),
+                QueueMode::Wait => wait_for_lock(&file, path)?,
+                QueueMode::Coalesce => {
+                    // Released as soon as this run owns the lock, so a request made while it
+                    // runs can queue again.
+                    let Some(_queued) = QueuedMarker::create(path) else {
+                        return Err(LockError::AlreadyQueued(owner));
+                    };
+                    wait_for_lock(&file, path)?;
+                }
+            },
+            Err(err) => return Err(err),
+        }
+
+        let guard = Self {
+            path: path.to_path_buf(),
+            file,
+            info: LockInfo {
+                job: job.to_string(),
+                pid: std::process::id(),
+                started_at: Utc::now(),
+                trigger,
+                child_pgid: None,
+            },
+        };
+        guard.write_info(&guard.info).map_err(LockError::Other)?;
+        // A cancel aimed at a previous run must not stop this one.
+        let _ = fs::remove_file(cancel_marker_path(path));
+        let _ = fs::remove_file(progress_path(path));
+        Ok(guard)
+    }
+
+    fn write_info(&self, info: &LockInfo) -> Result<()> {
+        let content = serde_json::to_string(info)?;
+        let mut file = &self.file;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(content.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Publish (or clear) the process group of the rclone invocation currently running.
+    fn record_child(&self, pgid: Option<u32>) {
+        let info = LockInfo {
+            child_pgid: pgid,
+            ..self.info.clone()
+        };
+        let _ = self.write_info(&info);
+    }
+
+    fn cancel_requested(&self) -> bool {
+        cancel_marker_path(&self.path).exists()
+    }
+
+    /// Best-effort: progress is informational and must never fail a sync.
+    fn record_progress(&self, progress: &SyncProgress) {
+        let Ok(content) = serde_json::to_vec(progress) else {
+            return;
+        };
+        // Write then rename so readers never see a half-written file.
+        let path = progress_path(&self.path);
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        if fs::write(&tmp, content).is_ok() {
+            let _ = fs::rename(&tmp, &path);
+        }
     }
 }
 
 impl Drop for LockGuard {
     fn drop(&mut self) {
-        let _ = fs::remove_file(&self.path);
+        let _ = fs::remove_file(cancel_marker_path(&self.path));
+        let _ = fs::remove_file(progress_path(&self.path));
+        // Cleared while still locked, so the file never names a finished run as its owner.
+        let _ = self.file.set_len(0);
+        // The flock is released when `file` is closed.
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct RunningInfo {
+    pub job: String,
+    pub pid: u32,
+    pub trigger: Trigger,
     pub started_at: Option<DateTime<Utc>>,
 }
 
-/// Detect whether a sync is currently in progress by consulting the job lock file.
+/// Detect whether a sync is currently in progress by probing the job lock file.
 ///
-/// Returns `None` when:
-/// - the lock file doesn't exist
-/// - the lock file PID is dead (and the stale lock is removed best-effort)
-pub fn detect_running(lock_file: &str) -> Option<RunningInfo> {
-    let path = expand_home(lock_file);
-    let pid = fs::read_to_string(&path).ok()?.trim().parse::<u32>().ok()?;
-
-    if !pid_alive(pid) {
-        let _ = fs::remove_file(&path);
-        return None;
+/// Returns `None` when the lock file doesn't exist or no process holds its lock.
+pub fn detect_running(lock_file: &Path) -> Option<RunningInfo> {
+    let owner = lock_owner(lock_file)?;
+    Some(RunningInfo {
+        job: owner.job,
+        pid: owner.pid,
+        trigger: owner.trigger,
+        started_at: Some(owner.started_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_stats_at_the_run_log_level() {
+        let args = |extra: &[&str]| -> Vec<String> {
+            build_command(&JobConfig::empty("docs"), "/a", "r:b", extra)
+                .unwrap()
+                .get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect()
+        };
+        let plain = args(&[]);
+        let idx = plain.iter().position(|a| a == "--stats-log-level").unwrap();
+        assert_eq!(plain[idx + 1], "NOTICE");
+        assert!(!args(&["-v"]).contains(&"--stats-log-level".to_string()));
+        assert!(!args(&["--log-level=INFO"]).contains(&"--stats-log-level".to_string()));
     }
 
-    let started_at = fs::metadata(&path)
-        .and_then(|m| m.modified())
-        .ok()
-        .and_then(|st| {
-            let secs = st.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs();
-            chrono::DateTime::from_timestamp(secs as i64, 0)
-        });
+    #[test]
+    fn finds_pairs_by_index_or_local_path() {
+        let cfg = JobConfig {
+            local_path: "/home/me".into(),
+            remote: "gdrive:".into(),
+            pairs: vec![
+                SyncPair {
+                    local: "Documents".into(),
+                    remote: String::new(),
+                },
+                SyncPair {
+                    local: "/srv/photos".into(),
+                    remote: "nas:photos".into(),
+                },
+            ],
+            ..JobConfig::empty("docs")
+        };
+        assert_eq!(find_pair(&cfg, "2").unwrap(), 1);
+        assert_eq!(find_pair(&cfg, "Documents").unwrap(), 0);
+        assert_eq!(find_pair(&cfg, "/home/me/Documents/").unwrap(), 0);
+        assert_eq!(find_pair(&cfg, "/srv/photos").unwrap(), 1);
+        assert!(find_pair(&cfg, "0").is_err());
+        assert!(find_pair(&cfg, "3").is_err());
+        assert!(find_pair(&cfg, "Music").is_err());
+    }
 
-    Some(RunningInfo { started_at })
+    #[test]
+    fn lock_reports_owner_and_is_released_on_drop() {
+        let dir =
+            std::env::temp_dir().join(format!("rclone-sync-helper-test-{}", std::process::id()));
+        let path = dir.join("docs.lock");
+
+        let guard = LockGuard::acquire(&path, "docs", Trigger::Timer, QueueMode::Skip).unwrap();
+        let running = detect_running(&path).unwrap();
+        assert_eq!(running.job, "docs");
+        assert_eq!(running.trigger, Trigger::Timer);
+        assert_eq!(running.pid, std::process::id());
+
+        match LockGuard::acquire(&path, "docs", Trigger::Applet, QueueMode::Skip) {
+            Err(LockError::AlreadyRunning(owner)) => assert_eq!(owner.trigger, Trigger::Timer),
+            other => panic!("expected AlreadyRunning, got {other:?}"),
+        }
+
+        // One run may queue behind the owner; a second request coalesces into it.
+        let queued = QueuedMarker::create(&path).unwrap();
+        match LockGuard::acquire(&path, "docs", Trigger::Applet, QueueMode::Coalesce) {
+            Err(LockError::AlreadyQueued(owner)) => assert_eq!(owner.pid, std::process::id()),
+            other => panic!("expected AlreadyQueued, got {other:?}"),
+        }
+        drop(queued);
+        assert!(!queued_marker_path(&path).exists());
+
+        drop(guard);
+        assert!(path.exists());
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        assert!(detect_running(&path).is_none());
+        assert!(LockGuard::acquire(&path, "docs", Trigger::Applet, QueueMode::Wait).is_ok());
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
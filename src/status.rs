@@ -8,8 +8,10 @@ use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 
+use crate::bisync_log::{self, BisyncEvent, Counter, StatsBlock};
+use crate::history::{self, HistoryRecord};
 use crate::job_config::JobConfig;
-use crate::runner::{RunResult, run_job};
+use crate::runner::{PairResult, RunOptions, RunOutcome, RunResult, Trigger, run_job};
 
 const STATE_FILE_NAME: &str = "status.json";
 const PROJECT_QUALIFIER: &str = "io";
@@ -32,6 +34,26 @@ pub struct SyncState {
     pub last_changed_count: Option<u32>,
     #[serde(default)]
     pub last_duration_secs: Option<u64>,
+    #[serde(default)]
+    pub last_outcome: Option<RunOutcome>,
+    /// Per-pair breakdown of the last run.
+    #[serde(default)]
+    pub last_pairs: Vec<PairResult>,
+    /// Result of the most recent dry run. Kept apart from the real run fields above.
+    #[serde(default)]
+    pub last_preview: Option<DryRunPreview>,
+    /// When a run was last skipped because the job was already running. The fields above keep
+    /// describing the last run that did something.
+    #[serde(default)]
+    pub last_skipped_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DryRunPreview {
+    pub timestamp: DateTime<Utc>,
+    pub exit_code: i32,
+    pub log_file: Option<String>,
+    pub pairs: Vec<PairResult>,
 }
 
 impl Default for SyncState {
@@ -47,21 +69,39 @@ impl Default for SyncState {
             last_log_file: None,
             last_changed_count: None,
             last_duration_secs: None,
+            last_outcome: None,
+            last_pairs: Vec::new(),
+            last_preview: None,
+            last_skipped_at: None,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ScriptResult {
     pub timestamp: DateTime<Utc>,
+    pub trigger: Trigger,
     pub exit_code: i32,
+    pub outcome: RunOutcome,
+    /// Raw rclone output; left out of JSON output (it is in the log file).
+    #[serde(skip)]
     pub stdout: String,
+    #[serde(skip)]
     pub stderr: String,
     pub log_file: Option<String>,
     pub duration_secs: Option<u64>,
+    pub dry_run: bool,
+    /// Only one of the job's pairs was run (`run --pair`).
+    #[serde(skip)]
+    pub single_pair: bool,
+    pub pairs: Vec<PairResult>,
 }
 
 impl ScriptResult {
+    pub fn changed_count(&self) -> Option<u32> {
+        detect_changed_count(self)
+    }
+
     fn preview_lines(&self) -> Vec<String> {
         let mut queue = VecDeque::with_capacity(MAX_LOG_LINES);
 
@@ -79,7 +119,23 @@ impl ScriptResult {
         queue.into()
     }
 
-    fn error_summary(&self) -> Option<String> {
+    pub fn error_summary(&self) -> Option<String> {
+        if let Some((idx, pair)) = self
+            .pairs
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.error.is_some())
+        {
+            return Some(format!(
+                "pair {}/{} ({} <-> {}): {}",
+                idx + 1,
+                self.pairs.len(),
+                pair.local,
+                pair.remote,
+                pair.error.as_deref().unwrap_or_default()
+            ));
+        }
+
         let stderr = self.stderr.trim();
         if !stderr.is_empty() {
             return stderr.lines().last().map(|line| line.trim().to_string());
@@ -124,10 +180,21 @@ impl StatusStore {
         Ok(())
     }
 
-    pub fn run_sync(&mut self, job_cfg: &JobConfig) -> Result<ScriptResult> {
-        let result = run_job_and_capture(job_cfg)?;
+    pub fn run_sync(&mut self, job_cfg: &JobConfig, opts: &RunOptions) -> Result<ScriptResult> {
+        let result = run_job_and_capture(job_cfg, opts)?;
+        // Another run may have finished (and persisted) while this one waited for the lock.
+        if let Ok(content) = fs::read_to_string(&self.state_path)
+            && let Ok(state) = serde_json::from_str(&content)
+        {
+            self.state = state;
+        }
         self.state.update_from_result(&result);
         self.persist()?;
+        // Previews are not runs; everything else (including skips) goes into the history.
+        // Best-effort: a history write failure must not turn a finished sync into an error.
+        if !result.dry_run {
+            let _ = history::append(&HistoryRecord::from_result(&job_cfg.name, &result));
+        }
         Ok(result)
     }
 
@@ -139,24 +206,68 @@ impl StatusStore {
 
 impl SyncState {
     fn update_from_result(&mut self, result: &ScriptResult) {
+        // A skipped run did nothing; keep the details of the last real run.
+        if result.outcome == RunOutcome::Skipped {
+            if !result.dry_run {
+                self.last_skipped_at = Some(result.timestamp);
+            }
+            return;
+        }
+
+        // Dry runs only refresh the preview; they must not look like a sync happened.
+        if result.dry_run {
+            if result.outcome != RunOutcome::Cancelled {
+                self.last_preview = Some(DryRunPreview {
+                    timestamp: result.timestamp,
+                    exit_code: result.exit_code,
+                    log_file: result.log_file.clone(),
+                    pairs: result.pairs.clone(),
+                });
+            }
+            return;
+        }
+
+        // A single-pair run only replaces that pair's result; the job-level fields (and the last
+        // success) describe runs of the whole job.
+        if result.single_pair {
+            for pair in &result.pairs {
+                match self
+                    .last_pairs
+                    .iter_mut()
+                    .find(|p| p.local == pair.local && p.remote == pair.remote)
+                {
+                    Some(last) => *last = pair.clone(),
+                    None => self.last_pairs.push(pair.clone()),
+                }
+            }
+            return;
+        }
+
         self.last_run = Some(result.timestamp);
         self.last_exit_code = Some(result.exit_code);
         self.log_preview = result.preview_lines();
         self.remote_summary = detect_remote_summary(result);
         self.last_log_file = result.log_file.clone();
         self.last_changed_count = detect_changed_count(result);
+        self.last_pairs = result.pairs.clone();
         self.last_duration_secs = result.duration_secs;
+        self.last_outcome = Some(result.outcome);
 
-        if result.exit_code == 0 {
-            self.last_success = Some(result.timestamp);
-            self.last_error = None;
-        } else {
-            self.last_error = result.error_summary();
+        match result.outcome {
+            RunOutcome::Success => {
+                self.last_success = Some(result.timestamp);
+                self.last_error = None;
+            }
+            RunOutcome::Failed => self.last_error = result.error_summary(),
+            // A user-initiated stop is neither a success nor an error.
+            RunOutcome::Cancelled => self.last_error = None,
+            RunOutcome::Skipped => {}
         }
     }
 }
 
-fn state_file_path(job: &str) -> Result<PathBuf> {
+/// The app's state directory (`$XDG_STATE_HOME/<app>`), created if missing.
+pub fn state_dir() -> Result<PathBuf> {
     let dir = if let Some(project_dirs) =
         ProjectDirs::from(PROJECT_QUALIFIER, PROJECT_ORGANIZATION, PROJECT_APPLICATION)
     {
@@ -178,55 +289,115 @@ fn state_file_path(job: &str) -> Result<PathBuf> {
     };
 
     fs::create_dir_all(&dir)?;
-    Ok(dir.join(format!("{}-{}", job, STATE_FILE_NAME)))
+    Ok(dir)
+}
+
+pub fn state_file_path(job: &str) -> Result<PathBuf> {
+    Ok(state_dir()?.join(format!("{}-{}", job, STATE_FILE_NAME)))
+}
+
+/// Move a job's cached state to a new job name.
+pub fn rename_state(old: &str, new: &str) -> Result<()> {
+    let old_path = state_file_path(old)?;
+    if !old_path.exists() {
+        return Ok(());
+    }
+    let mut store = StatusStore::load(old)?;
+    store.state.job = new.to_string();
+    store.state_path = state_file_path(new)?;
+    store.persist()?;
+    fs::remove_file(&old_path)
+        .with_context(|| format!("Failed to remove {}", old_path.display()))?;
+    Ok(())
+}
+
+pub fn remove_state(job: &str) -> Result<()> {
+    let path = state_file_path(job)?;
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
 }
 
-fn run_job_and_capture(job_cfg: &JobConfig) -> Result<ScriptResult> {
-    let result: RunResult = run_job(job_cfg)?;
+fn run_job_and_capture(job_cfg: &JobConfig, opts: &RunOptions) -> Result<ScriptResult> {
+    let result: RunResult = run_job(job_cfg, opts)?;
     Ok(ScriptResult {
         timestamp: result.timestamp,
+        trigger: result.trigger,
         exit_code: result.exit_code,
+        outcome: result.outcome,
         stdout: result.stdout,
         stderr: result.stderr,
         log_file: result.log_file,
         duration_secs: result.duration_secs,
+        dry_run: result.dry_run,
+        single_pair: opts.pair.is_some(),
+        pairs: result.pairs,
     })
 }
 
 fn detect_remote_summary(result: &ScriptResult) -> Option<String> {
-    for line in result.stdout.lines().chain(result.stderr.lines()) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        let candidate = trimmed.to_lowercase();
-        if candidate.contains("remote")
-            || candidate.contains("drive")
-            || candidate.contains("gdrive")
-            || candidate.contains("sync")
-        {
-            return Some(trimmed.to_string());
-        }
-    }
-    None
+    bisync_log::parse_output(&result.stdout, &result.stderr)
+        .into_iter()
+        .find_map(|event| match event {
+            BisyncEvent::Log { message, .. } => {
+                let candidate = message.to_lowercase();
+                (candidate.contains("remote")
+                    || candidate.contains("drive")
+                    || candidate.contains("gdrive")
+                    || candidate.contains("sync"))
+                .then_some(message)
+            }
+            _ => None,
+        })
 }
 
 fn detect_changed_count(result: &ScriptResult) -> Option<u32> {
-    // Extract numeric counters from rclone bisync output.
-    let combined = format!("{}\n{}", result.stdout, result.stderr);
+    // Structured per-pair counters are authoritative when bisync reported them.
+    let per_pair: Vec<u32> = result
+        .pairs
+        .iter()
+        .filter_map(|p| p.changed_count())
+        .collect();
+    if !per_pair.is_empty() {
+        return Some(per_pair.into_iter().fold(0u32, u32::saturating_add));
+    }
+
+    let events = bisync_log::parse_output(&result.stdout, &result.stderr);
 
-    // Prefer per-pair "Path1/Path2: N changes:" counters. These appear once per bisync run and
-    // work well with multi-pair jobs (we can sum across pairs).
-    let (changes_total, saw_changes_lines) = sum_path_changes(&combined);
-    if saw_changes_lines {
+    // Prefer "Path1/Path2: N changes:" counters. These appear once per bisync run and work well
+    // with multi-pair output (we can sum across pairs).
+    let mut saw_changes = false;
+    let mut changes_total: u32 = 0;
+    for event in &events {
+        if let BisyncEvent::Changes { summary, .. } = event {
+            saw_changes = true;
+            changes_total = changes_total.saturating_add(summary.total);
+        }
+    }
+    if saw_changes {
         return Some(changes_total);
     }
 
-    // Fallback: Look for "Transferred:" and "Copied:" labels with completion counts.
+    // Without INFO logging only the stats are left: use the JSON stats rclone reported per pair.
+    let stats: Vec<_> = result
+        .pairs
+        .iter()
+        .filter_map(|p| p.stats.as_ref())
+        .collect();
+    if !stats.is_empty() {
+        let total = stats
+            .iter()
+            .map(|s| s.transfers.saturating_add(s.deletes))
+            .fold(0u64, u64::saturating_add);
+        return Some(u32::try_from(total).unwrap_or(u32::MAX));
+    }
+
+    // Last resort: transfer/copy counters from text stats blocks (output without JSON logging).
     // This is less reliable for multi-pair runs and also doesn't count deletions, but it helps
-    // for logs where change counters aren't present.
-    let transferred = extract_last_number_after_label(&combined, "Transferred:");
-    let copied = extract_last_number_after_label(&combined, "Copied:");
+    // for logs where change counters aren't present (no INFO logging).
+    let transferred = last_completed_total(&events, |b| b.transfers);
+    let copied = last_completed_total(&events, |b| b.copied);
 
     // Sum them if both are present, otherwise return whichever is found.
     match (transferred, copied) {
@@ -237,83 +408,90 @@ fn detect_changed_count(result: &ScriptResult) -> Option<u32> {
     }
 }
 
-fn sum_path_changes(text: &str) -> (u32, bool) {
-    let mut total: u32 = 0;
-    let mut saw_any = false;
-
-    for line in text.lines() {
-        // Example:
-        // 2026/01/11 22:25:26 INFO  : Path1:   40 changes:    4 new,   36 newer,    0 older,    0 deleted
-        for label in ["Path1:", "Path2:"] {
-            let Some(pos) = line.find(label) else { continue };
-            let after = line[pos + label.len()..].trim_start();
-            let mut parts = after.split_whitespace();
-            let Some(num_str) = parts.next() else { continue };
-            let Some(changes_str) = parts.next() else { continue };
-            if !changes_str.starts_with("changes") {
-                continue;
-            }
-            if let Ok(n) = num_str.parse::<u32>() {
-                total = total.saturating_add(n);
-                saw_any = true;
-            }
-        }
-    }
-
-    (total, saw_any)
-}
-
-fn extract_last_number_after_label(text: &str, label: &str) -> Option<u32> {
+/// Total of the last counter that reached 100% (e.g. `Transferred: 262 / 262, 100%` -> 262),
+/// falling back to the last counter seen at any progress.
+fn last_completed_total(
+    events: &[BisyncEvent],
+    counter: impl Fn(&StatsBlock) -> Option<Counter>,
+) -> Option<u32> {
     let mut last_100_percent: Option<u32> = None;
     let mut last_any_percent: Option<u32> = None;
 
-    for line in text.lines() {
-        let line = line.trim();
-        if let Some(rest) = line.strip_prefix(label) {
-            let rest = rest.trim();
-            // Look for "X / Y" format with percentage
-            if rest.contains(" / ") {
-                if let Some(slash_pos) = rest.find(" / ") {
-                    let after_slash = &rest[slash_pos + 3..];
-                    if let Some(comma_pos) = after_slash.find(',') {
-                        let num_str = after_slash[..comma_pos].trim();
-                        if let Ok(num) = num_str.parse::<u32>() {
-                            // Prefer lines with "100%" (rclone bisync completion format)
-                            // e.g., "Transferred:          262 / 262, 100%" -> extract 262
-                            if rest.contains("100%") {
-                                last_100_percent = Some(num);
-                            } else {
-                                // Track any percentage line as fallback
-                                // e.g., "Transferred: 52 / 262, 20%" -> extract 262
-                                last_any_percent = Some(num);
-                            }
-                        }
-                    }
-                }
-            }
+    for c in bisync_log::stats_blocks(events).filter_map(counter) {
+        if c.percent == Some(100) {
+            last_100_percent = Some(c.total);
+        } else {
+            last_any_percent = Some(c.total);
         }
     }
 
-    // Return 100% line if found, otherwise any percentage line, otherwise None
     last_100_percent.or(last_any_percent)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bisync_log::{ChangeSummary, RcloneStats};
     use chrono::TimeZone;
 
+    fn pair_result(local: &str, remote: &str, exit_code: i32, error: Option<&str>) -> PairResult {
+        PairResult {
+            local: local.into(),
+            remote: remote.into(),
+            attempts: Vec::new(),
+            exit_code,
+            duration_secs: 1,
+            path1: None,
+            path2: None,
+            error: error.map(str::to_string),
+            changes: Vec::new(),
+            stats: None,
+        }
+    }
+
     fn sample_result(exit_code: i32, stdout: &str, stderr: &str) -> ScriptResult {
         ScriptResult {
             timestamp: Utc.with_ymd_and_hms(2024, 1, 5, 12, 0, 0).unwrap(),
+            trigger: Trigger::Manual,
             exit_code,
+            outcome: if exit_code == 0 {
+                RunOutcome::Success
+            } else {
+                RunOutcome::Failed
+            },
             stdout: stdout.to_string(),
             stderr: stderr.to_string(),
             log_file: None,
             duration_secs: Some(123),
+            dry_run: false,
+            single_pair: false,
+            pairs: Vec::new(),
         }
     }
 
+    #[test]
+    fn single_pair_run_only_updates_that_pair() {
+        let mut state = SyncState::default();
+        let mut full = sample_result(2, "", "");
+        full.pairs = vec![
+            pair_result("/a", "r:a", 0, None),
+            pair_result("/b", "r:b", 2, Some("boom")),
+        ];
+        state.update_from_result(&full);
+        let (last_run, last_error) = (state.last_run, state.last_error.clone());
+
+        let mut single = sample_result(0, "", "");
+        single.single_pair = true;
+        single.timestamp = Utc.with_ymd_and_hms(2024, 1, 6, 12, 0, 0).unwrap();
+        single.pairs = vec![pair_result("/b", "r:b", 0, None)];
+        state.update_from_result(&single);
+        assert_eq!(state.last_success, None);
+        assert_eq!(state.last_run, last_run);
+        assert_eq!(state.last_error, last_error);
+        assert_eq!(state.last_pairs.len(), 2);
+        assert_eq!(state.last_pairs[1].exit_code, 0);
+    }
+
     #[test]
     fn preview_lines_limits_log_and_ignores_empty() {
         let result = sample_result(0, "line1\n\nline2\nline3\nline4\nline5\nline6\nline7", "");
@@ -329,6 +507,49 @@ mod tests {
         assert_eq!(result.error_summary().as_deref(), Some("more"));
     }
 
+    #[test]
+    fn error_summary_names_failing_pair() {
+        let mut result = sample_result(1, "", "last line");
+        result.pairs = vec![
+            pair_result("/home/u/a", "gdrive:a", 0, None),
+            pair_result("/home/u/b", "gdrive:b", 1, Some("ERROR : boom")),
+        ];
+        assert_eq!(
+            result.error_summary().as_deref(),
+            Some("pair 2/2 (/home/u/b <-> gdrive:b): ERROR : boom")
+        );
+    }
+
+    #[test]
+    fn detect_changed_count_prefers_pair_results() {
+        let mut result = sample_result(0, "", "Transferred:          262 / 262, 100%");
+        let mut a = pair_result("/a", "r:a", 0, None);
+        a.path1 = Some(ChangeSummary {
+            total: 3,
+            ..Default::default()
+        });
+        let mut b = pair_result("/b", "r:b", 0, None);
+        b.path2 = Some(ChangeSummary {
+            total: 4,
+            ..Default::default()
+        });
+        result.pairs = vec![a, b, pair_result("/c", "r:c", 0, None)];
+        assert_eq!(detect_changed_count(&result), Some(7));
+    }
+
+    #[test]
+    fn detect_changed_count_uses_json_stats_over_text() {
+        let mut result = sample_result(0, "", "Transferred:          262 / 262, 100%");
+        let mut a = pair_result("/a", "r:a", 0, None);
+        a.stats = Some(RcloneStats {
+            transfers: 5,
+            deletes: 2,
+            ..Default::default()
+        });
+        result.pairs = vec![a, pair_result("/b", "r:b", 0, None)];
+        assert_eq!(detect_changed_count(&result), Some(7));
+    }
+
     #[test]
     fn detect_remote_summary_matches_drive_keywords() {
         let result = sample_result(0, "Syncing local -> Remote Drive", "");
@@ -352,6 +573,58 @@ mod tests {
         assert_eq!(state.last_exit_code, Some(2));
     }
 
+    #[test]
+    fn dry_run_only_updates_preview() {
+        let mut state = SyncState::default();
+        state.update_from_result(&sample_result(0, "", ""));
+        let before = state.clone();
+
+        let mut preview = sample_result(0, "", "");
+        preview.timestamp = Utc.with_ymd_and_hms(2024, 1, 6, 12, 0, 0).unwrap();
+        preview.dry_run = true;
+        state.update_from_result(&preview);
+        assert_eq!(state.last_success, before.last_success);
+        assert_eq!(state.last_run, before.last_run);
+        assert_eq!(
+            state.last_preview.map(|p| p.timestamp),
+            Some(preview.timestamp)
+        );
+    }
+
+    #[test]
+    fn sync_state_records_cancel_without_error() {
+        let mut state = SyncState::default();
+        state.update_from_result(&sample_result(0, "", ""));
+        let last_success = state.last_success;
+
+        let mut cancelled = sample_result(143, "", "Terminated");
+        cancelled.outcome = RunOutcome::Cancelled;
+        state.update_from_result(&cancelled);
+        assert_eq!(state.last_outcome, Some(RunOutcome::Cancelled));
+        assert!(state.last_error.is_none());
+        assert_eq!(state.last_success, last_success);
+    }
+
+    #[test]
+    fn skipped_run_keeps_last_success_and_error() {
+        let mut state = SyncState::default();
+        state.update_from_result(&sample_result(0, "", ""));
+        state.update_from_result(&sample_result(2, "", "failed"));
+        let last_success = state.last_success;
+
+        let mut skipped = sample_result(0, "", "Sync already running. Skipping this run.");
+        skipped.outcome = RunOutcome::Skipped;
+        skipped.timestamp = Utc.with_ymd_and_hms(2024, 1, 6, 12, 0, 0).unwrap();
+        let last_run = state.last_run;
+        state.update_from_result(&skipped);
+        assert_eq!(state.last_skipped_at, Some(skipped.timestamp));
+        assert_eq!(state.last_run, last_run);
+        assert_eq!(state.last_outcome, Some(RunOutcome::Failed));
+        assert_eq!(state.last_success, last_success);
+        assert_eq!(state.last_error.as_deref(), Some("failed"));
+        assert_eq!(state.last_exit_code, Some(2));
+    }
+
     #[test]
     fn detect_changed_count_extracts_from_rclone_bisync_format() {
         // Test with rclone bisync output format - should extract total from 100% line
@@ -389,28 +662,28 @@ Transferred:   	          0 B / 0 B, -, 0 B/s, ETA -"#;
     }
 
     #[test]
-    fn extract_last_number_prefers_100_percent_line() {
+    fn last_completed_total_prefers_100_percent_line() {
         let text = r#"Transferred:           52 / 262, 20%
 Transferred:          195 / 262, 74%
 Transferred:          262 / 262, 100%"#;
-        let result = extract_last_number_after_label(text, "Transferred:");
+        let result = last_completed_total(&bisync_log::parse(text), |b| b.transfers);
         assert_eq!(result, Some(262), "Should extract from the 100% line");
     }
 
     #[test]
-    fn extract_last_number_handles_multiple_100_percent_lines() {
+    fn last_completed_total_handles_multiple_100_percent_lines() {
         // If there are multiple 100% lines, take the last one
         let text = r#"Transferred:          100 / 100, 100%
 Transferred:          262 / 262, 100%"#;
-        let result = extract_last_number_after_label(text, "Transferred:");
+        let result = last_completed_total(&bisync_log::parse(text), |b| b.transfers);
         assert_eq!(result, Some(262), "Should extract from the last 100% line");
     }
 
     #[test]
-    fn extract_last_number_falls_back_to_any_percentage() {
+    fn last_completed_total_falls_back_to_any_percentage() {
         // If no 100% line exists, use any percentage line
         let text = r#"Transferred:           52 / 262, 20%"#;
-        let result = extract_last_number_after_label(text, "Transferred:");
+        let result = last_completed_total(&bisync_log::parse(text), |b| b.transfers);
         assert_eq!(
             result,
             Some(262),
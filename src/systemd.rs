@@ -3,15 +3,50 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+use crate::job_config::{Resources, Schedule};
+use crate::runner::{self, Trigger};
+use crate::systemd_dbus;
+
+/// Lines of the service's journal kept with a failed run.
+const JOURNAL_LINES: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct TimerStatus {
     pub unit: String,
     pub installed: bool,
     pub enabled: bool,
     pub active: bool,
     pub next_elapse: Option<String>,
+    /// When the timer last fired.
+    pub last_trigger: Option<String>,
+    /// systemd's `Result` for the service's last run (`success`, `exit-code`, ...).
+    pub service_result: Option<String>,
+    /// Exit status of the service's last run.
+    pub exit_status: Option<i32>,
+    /// Last lines the service logged to the journal; only fetched when its last run failed.
+    pub journal_tail: Vec<String>,
+    /// Installed units that differ from what `install_units` would write now.
+    pub outdated: Vec<String>,
+    /// Why the user manager couldn't be asked; everything but `installed`, `enabled` and
+    /// `outdated` is then unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager_error: Option<String>,
+}
+
+impl TimerStatus {
+    /// Outcome of the service's last run, e.g. `Succeeded` or `Failed: exit-code (status 2)`.
+    pub fn last_result(&self) -> Option<String> {
+        let result = self.service_result.as_deref()?;
+        if result == "success" {
+            return Some("Succeeded".into());
+        }
+        Some(match self.exit_status.filter(|s| *s != 0) {
+            Some(status) => format!("Failed: {result} (status {status})"),
+            None => format!("Failed: {result}"),
+        })
+    }
 }
 
 pub struct SystemdUser {
@@ -26,50 +61,211 @@ impl SystemdUser {
         Ok(Self { systemd_user_dir })
     }
 
+    /// Write the shared template units and the job's schedule drop-in. Per-job unit files from
+    /// older versions are replaced, keeping the timer enabled if it was.
     pub fn install_units(&self, job: &str) -> Result<()> {
-        let service_name = service_unit_name(job);
-        let timer_name = timer_unit_name(job);
+        let cfg = crate::jobs::load_existing(job)?;
+        validate_schedule(&cfg.schedule)?;
+        validate_resources(&cfg.resources)?;
+        let legacy = self.legacy_units(job);
+        let legacy_enabled = legacy.iter().any(|unit| {
+            self.systemd_user_dir
+                .join("timers.target.wants")
+                .join(unit)
+                .symlink_metadata()
+                .is_ok()
+        });
+        for unit in &legacy {
+            if unit.ends_with(".timer") {
+                let _ = systemctl_user(&["disable", "--now", unit]);
+            }
+            let path = self.systemd_user_dir.join(unit);
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
 
-        let service_path = self.systemd_user_dir.join(&service_name);
-        let timer_path = self.systemd_user_dir.join(&timer_name);
+        for (name, content) in render_units(job)? {
+            let path = self.systemd_user_dir.join(&name);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
 
-        let exe = std::env::current_exe().context("Failed to find current executable path")?;
+        self.daemon_reload()?;
+        if legacy_enabled {
+            self.enable_timer(job)?;
+        } else {
+            // A running timer keeps its old triggers until restarted.
+            let _ = systemctl_user(&["try-restart", &timer_unit_name(job)]);
+        }
+        Ok(())
+    }
 
-        let service = format!(
-            r#"[Unit]
-Description=Rclone bisync job ({job})
+    /// Unit files whose content differs from what `install_units` would write now (e.g. after
+    /// the binary moved or the schedule changed) or that are missing, plus per-job unit files
+    /// left by older versions. Empty if the job has no units at all.
+    pub fn outdated_units(&self, job: &str) -> Result<Vec<String>> {
+        if !self.units_installed(job) {
+            return Ok(vec![]);
+        }
+        let mut outdated = self.legacy_units(job);
+        for (name, content) in render_units(job)? {
+            let path = self.systemd_user_dir.join(&name);
+            if fs::read_to_string(&path).ok().as_ref() != Some(&content) {
+                outdated.push(name);
+            }
+        }
+        Ok(outdated)
+    }
 
-[Service]
-Type=oneshot
-ExecStart={exe} run --job {job}
-"#,
-            job = job,
-            exe = exe.display()
-        );
+    /// Move jobs still using per-job unit files onto the template units. A job that fails does
+    /// not stop the others; returns each migrated job with its outcome.
+    pub fn migrate_legacy_units(&self) -> Result<Vec<(String, Result<()>)>> {
+        Ok(self
+            .installed_jobs()?
+            .into_iter()
+            .filter(|job| {
+                !self.legacy_units(job).is_empty() && crate::jobs::load_existing(job).is_ok()
+            })
+            .map(|job| {
+                let res = self.install_units(&job);
+                (job, res)
+            })
+            .collect())
+    }
 
-        let timer = format!(
-            r#"[Unit]
-Description=Run rclone bisync job ({job}) hourly
+    /// Per-job `rclonesync-helper@<job>.service`/`.timer` files written by older versions,
+    /// which take precedence over the template. They used the job name unescaped.
+    fn legacy_units(&self, job: &str) -> Vec<String> {
+        let mut names = vec![job.to_string(), escape_instance(job)];
+        names.dedup();
+        names
+            .iter()
+            .flat_map(|name| {
+                [
+                    format!("{UNIT_PREFIX}{name}.timer"),
+                    format!("{UNIT_PREFIX}{name}.service"),
+                ]
+            })
+            .filter(|unit| self.systemd_user_dir.join(unit).is_file())
+            .collect()
+    }
 
-[Timer]
-OnCalendar=hourly
-Persistent=true
-Unit={service_name}
+    /// Rewrite the job's units if they drifted from what `install_units` generates today.
+    /// The timer keeps its enabled state. Returns the units that were out of date.
+    pub fn repair_units(&self, job: &str) -> Result<Vec<String>> {
+        if !self.units_installed(job) {
+            anyhow::bail!("Units for job {job} are not installed; install them first");
+        }
+        let outdated = self.outdated_units(job)?;
+        if !outdated.is_empty() {
+            self.install_units(job)?;
+        }
+        Ok(outdated)
+    }
 
-[Install]
-WantedBy=timers.target
-"#,
-            job = job,
-            service_name = service_name
-        );
+    /// Jobs that have units installed, whether or not their config still exists.
+    pub fn installed_jobs(&self) -> Result<Vec<String>> {
+        let mut jobs: Vec<String> = fs::read_dir(&self.systemd_user_dir)
+            .with_context(|| format!("Failed to read {}", self.systemd_user_dir.display()))?
+            .filter_map(|ent| ent.ok())
+            .filter_map(|ent| {
+                let name = ent.file_name().to_string_lossy().into_owned();
+                match name.strip_suffix(".d") {
+                    // Drop-in directory of an instance.
+                    Some(unit) => job_from_unit(unit),
+                    // Legacy per-job unit file, named after the raw job name.
+                    None if ent.path().is_file() => instance_of(&name).map(str::to_string),
+                    None => None,
+                }
+            })
+            .collect();
+        jobs.sort();
+        jobs.dedup();
+        Ok(jobs)
+    }
 
-        fs::write(&service_path, service)
-            .with_context(|| format!("Failed to write {}", service_path.display()))?;
-        fs::write(&timer_path, timer)
-            .with_context(|| format!("Failed to write {}", timer_path.display()))?;
+    /// Whether the job's schedule drop-in (or a legacy unit file) exists, without asking systemd.
+    pub fn units_installed(&self, job: &str) -> bool {
+        self.systemd_user_dir.join(schedule_dropin(job)).exists()
+            || !self.legacy_units(job).is_empty()
+    }
 
-        self.daemon_reload()?;
-        Ok(())
+    /// Stop and disable the job's timer and delete its drop-ins, plus the templates once no
+    /// job uses them.
+    pub fn remove_units(&self, job: &str) -> Result<()> {
+        let _ = systemctl_user(&["disable", "--now", &timer_unit_name(job)]);
+        for unit in self.legacy_units(job) {
+            let path = self.systemd_user_dir.join(unit);
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        for dropin in [schedule_dropin(job), resources_dropin(job)] {
+            let dropin = self.systemd_user_dir.join(dropin);
+            if dropin.exists() {
+                fs::remove_file(&dropin)
+                    .with_context(|| format!("Failed to remove {}", dropin.display()))?;
+            }
+            // Leaves drop-ins the user added there.
+            if let Some(dir) = dropin.parent() {
+                let _ = fs::remove_dir(dir);
+            }
+        }
+        if self.installed_jobs()?.is_empty() {
+            for template in [template_unit_name("service"), template_unit_name("timer")] {
+                let _ = fs::remove_file(self.systemd_user_dir.join(template));
+            }
+        }
+        self.daemon_reload()
+    }
+
+    /// Start a sync of the job outside the calling process, so it keeps running if the applet
+    /// goes away. Uses the installed service when the job is idle; otherwise (no units, a run
+    /// in progress that starting the service would just join, or a service that skips runs on
+    /// battery) a transient `systemd-run --user` unit with the same resource limits that queues
+    /// behind it. Returns the unit running the sync.
+    pub fn start_sync(&self, job: &str, trigger: Trigger) -> Result<String> {
+        let cfg = crate::jobs::load_existing(job)?;
+        let running = runner::detect_running(&runner::lock_file_path(&cfg)).is_some();
+        if self.units_installed(job) && !running && !cfg.resources.require_ac_power {
+            let service = service_unit_name(job);
+            systemctl_user(&["start", "--no-block", &service])?;
+            return Ok(service);
+        }
+
+        let exe = std::env::current_exe().context("Failed to find current executable path")?;
+        // Unique per start: "Sync now" can be pressed again while a detached run is queued.
+        let now = chrono::Utc::now();
+        let unit = format!(
+            "rclonesync-helper-run-{}-{}.service",
+            escape_instance(job),
+            now.timestamp_nanos_opt()
+                .unwrap_or_else(|| now.timestamp_micros())
+        );
+        let mut cmd = Command::new("systemd-run");
+        cmd.args(["--user", "--collect", "--quiet"])
+            .arg(format!("--unit={unit}"))
+            .arg(format!("--description=Rclone bisync job ({job})"))
+            .arg(format!("--setenv={}={unit}", runner::UNIT_ENV));
+        for property in service_properties(&cfg.resources) {
+            cmd.arg("-p").arg(property);
+        }
+        let output = cmd
+            .arg(exe)
+            .args(["run", "--job", job, "--queue", "coalesce", "--trigger"])
+            .arg(trigger.label())
+            .output()
+            .context("Failed to run systemd-run")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "systemd-run failed (code {:?}): {}",
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(unit)
     }
 
     pub fn enable_timer(&self, job: &str) -> Result<()> {
@@ -85,35 +281,404 @@ WantedBy=timers.target
     pub fn status(&self, job: &str) -> Result<TimerStatus> {
         let unit = timer_unit_name(job);
         let service = service_unit_name(job);
-        let installed = self.systemd_user_dir.join(&unit).exists()
-            && self.systemd_user_dir.join(&service).exists();
-        let enabled = is_enabled(&unit)?;
-        let active = is_active(&unit)?;
-        // For calendar timers, `list-timers` is the most reliable user-facing representation.
-        let next_from_list = systemctl_list_timer_next(&unit).ok().flatten();
-        let next_elapse = systemctl_show_property(&unit, "NextElapseUSecRealtime")?;
+        let installed = self.units_installed(job);
+        let outdated = self.outdated_units(job).unwrap_or_default();
+        let props = match systemd_dbus::unit_properties(&unit, &service) {
+            Ok(props) => props,
+            // Without the user manager, report what the unit files on disk say.
+            Err(err) => {
+                return Ok(TimerStatus {
+                    enabled: installed && self.enabled_on_disk(job),
+                    unit,
+                    installed,
+                    active: false,
+                    next_elapse: None,
+                    last_trigger: None,
+                    service_result: None,
+                    exit_status: None,
+                    journal_tail: vec![],
+                    outdated,
+                    manager_error: Some(format!("{err:#}")),
+                });
+            }
+        };
+        // A run that fails before writing its log (bad config, moved binary) is only visible
+        // in the journal.
+        let failed = props
+            .service_result
+            .as_deref()
+            .is_some_and(|r| r != "success");
+        let journal_tail = if failed {
+            journal_tail(&service, JOURNAL_LINES).unwrap_or_default()
+        } else {
+            vec![]
+        };
 
         Ok(TimerStatus {
             unit,
             installed,
-            enabled,
-            active,
-            next_elapse: next_from_list.or_else(|| parse_next_elapse(next_elapse)),
+            enabled: props.enabled,
+            active: props.active,
+            next_elapse: props.next_elapse,
+            last_trigger: props.last_trigger,
+            service_result: props.service_result,
+            exit_status: props.exit_status,
+            journal_tail,
+            outdated,
+            manager_error: None,
         })
     }
 
+    /// Whether `timers.target` wants the job's timer (or its legacy per-job timer).
+    fn enabled_on_disk(&self, job: &str) -> bool {
+        let wants = self.systemd_user_dir.join("timers.target.wants");
+        std::iter::once(timer_unit_name(job))
+            .chain(
+                self.legacy_units(job)
+                    .into_iter()
+                    .filter(|u| u.ends_with(".timer")),
+            )
+            .any(|unit| wants.join(unit).symlink_metadata().is_ok())
+    }
+
     fn daemon_reload(&self) -> Result<()> {
         systemctl_user(&["daemon-reload"])?;
         Ok(())
     }
 }
 
+/// Whether the user's systemd instance can be reached over D-Bus.
+pub fn user_manager_reachable() -> Result<()> {
+    systemd_dbus::ping()
+}
+
+/// `(path under the unit dir, content)` of the service and timer templates and the job's
+/// schedule and resources drop-ins. `%I` is the unescaped instance, i.e. the job name.
+fn render_units(job: &str) -> Result<[(String, String); 4]> {
+    let cfg = crate::jobs::load_existing(job)?;
+    let schedule = cfg.schedule;
+
+    let exe = std::env::current_exe().context("Failed to find current executable path")?;
+
+    let service = format!(
+        r#"[Unit]
+Description=Rclone bisync job (%I)
+
+[Service]
+Type=oneshot
+Environment={unit_env}=%n
+ExecStart="{exe}" run --job "%I" --trigger timer
+"#,
+        unit_env = runner::UNIT_ENV,
+        exe = exe.display()
+    );
+
+    // The triggers come from each instance's schedule drop-in.
+    let timer = format!(
+        r#"[Unit]
+Description=Run rclone bisync job (%I)
+
+[Timer]
+Unit={UNIT_PREFIX}%i.service
+
+[Install]
+WantedBy=timers.target
+"#
+    );
+
+    let dropin = format!(
+        r#"[Unit]
+Description=Run rclone bisync job ({job}) {description}
+
+[Timer]
+{triggers}"#,
+        description = schedule.describe(),
+        triggers = timer_triggers(&schedule),
+    );
+
+    Ok([
+        (template_unit_name("service"), service),
+        (template_unit_name("timer"), timer),
+        (schedule_dropin(job), dropin),
+        (resources_dropin(job), render_resources(&cfg.resources)),
+    ])
+}
+
+/// The service drop-in with the job's resource limits and run conditions.
+fn render_resources(resources: &Resources) -> String {
+    let mut unit = Vec::new();
+    if resources.wait_for_network {
+        unit.push("Wants=network-online.target".to_string());
+        unit.push("After=network-online.target".to_string());
+    }
+    if resources.require_ac_power {
+        unit.push("ConditionACPower=true".to_string());
+    }
+    let mut out = String::new();
+    if !unit.is_empty() {
+        out.push_str("[Unit]\n");
+        unit.iter().for_each(|l| out.push_str(&format!("{l}\n")));
+        out.push('\n');
+    }
+    out.push_str("[Service]\n");
+    for property in service_properties(resources) {
+        out.push_str(&format!("{property}\n"));
+    }
+    out
+}
+
+/// `[Service]` properties for the job's resource limits, also passed to `systemd-run -p`.
+fn service_properties(resources: &Resources) -> Vec<String> {
+    let mut props = Vec::new();
+    if let Some(nice) = resources.nice {
+        props.push(format!("Nice={nice}"));
+    }
+    if let Some(class) = non_empty(&resources.io_scheduling_class) {
+        props.push(format!("IOSchedulingClass={class}"));
+    }
+    if let Some(weight) = resources.cpu_weight {
+        props.push(format!("CPUWeight={weight}"));
+    }
+    if let Some(weight) = resources.io_weight {
+        props.push(format!("IOWeight={weight}"));
+    }
+    if let Some(max) = non_empty(&resources.memory_max) {
+        props.push(format!("MemoryMax={max}"));
+    }
+    if let Some(timeout) = non_empty(&resources.timeout) {
+        props.push(format!("TimeoutStartSec={timeout}"));
+    }
+    props
+}
+
+/// The `[Timer]` lines that decide when a job runs, each ending in a newline.
+fn timer_triggers(schedule: &Schedule) -> String {
+    let mut lines = Vec::new();
+    match schedule.interval() {
+        // OnUnitActiveSec alone never fires before the service ran once, so also count from
+        // when the timer starts.
+        Some(interval) => {
+            lines.push(format!("OnActiveSec={interval}"));
+            lines.push(format!("OnUnitActiveSec={interval}"));
+        }
+        None => lines.push(format!("OnCalendar={}", schedule.on_calendar.trim())),
+    }
+    if let Some(delay) = non_empty(&schedule.randomized_delay) {
+        lines.push(format!("RandomizedDelaySec={delay}"));
+    }
+    if let Some(after) = non_empty(&schedule.on_boot) {
+        lines.push(format!("OnBootSec={after}"));
+    }
+    if let Some(after) = non_empty(&schedule.on_startup) {
+        lines.push(format!("OnStartupSec={after}"));
+    }
+    // Persistent= only applies to OnCalendar.
+    if schedule.interval().is_none() {
+        lines.push(format!("Persistent={}", schedule.persistent));
+    }
+    lines.iter().map(|l| format!("{l}\n")).collect()
+}
+
+fn non_empty(s: &Option<String>) -> Option<&str> {
+    s.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Check the schedule's calendar expression and time spans with `systemd-analyze`.
+pub fn validate_schedule(schedule: &Schedule) -> Result<()> {
+    if schedule.interval().is_none() {
+        if schedule.on_calendar.trim().is_empty() {
+            anyhow::bail!("Schedule needs an OnCalendar expression or an interval");
+        }
+        calendar_next_elapses(schedule.on_calendar.trim(), 1)?;
+    }
+    let spans = [
+        ("interval", &schedule.interval),
+        ("randomized delay", &schedule.randomized_delay),
+        ("on boot", &schedule.on_boot),
+        ("on startup", &schedule.on_startup),
+    ];
+    for (what, span) in spans {
+        if let Some(span) = non_empty(span) {
+            systemd_analyze(&["timespan", span])
+                .map_err(|err| anyhow::anyhow!("Invalid {what} '{span}': {err}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Check the resource settings against the ranges systemd accepts.
+pub fn validate_resources(resources: &Resources) -> Result<()> {
+    if let Some(nice) = resources.nice
+        && !(-20..=19).contains(&nice)
+    {
+        anyhow::bail!("Invalid nice {nice}: use -20 to 19");
+    }
+    if let Some(class) = non_empty(&resources.io_scheduling_class)
+        && !matches!(class, "idle" | "best-effort" | "realtime")
+    {
+        anyhow::bail!("Invalid IO scheduling class '{class}': use idle, best-effort or realtime");
+    }
+    for (what, weight) in [
+        ("CPU weight", resources.cpu_weight),
+        ("IO weight", resources.io_weight),
+    ] {
+        if let Some(weight) = weight
+            && !(1..=10000).contains(&weight)
+        {
+            anyhow::bail!("Invalid {what} {weight}: use 1 to 10000");
+        }
+    }
+    if let Some(max) = non_empty(&resources.memory_max)
+        && !valid_memory_size(max)
+    {
+        anyhow::bail!(
+            "Invalid memory limit '{max}': use bytes with K, M, G or T, a percentage or infinity"
+        );
+    }
+    if let Some(timeout) = non_empty(&resources.timeout) {
+        systemd_analyze(&["timespan", timeout])
+            .map_err(|err| anyhow::anyhow!("Invalid timeout '{timeout}': {err}"))?;
+    }
+    Ok(())
+}
+
+/// `MemoryMax=` values: `infinity`, a percentage or a byte count with an optional suffix.
+fn valid_memory_size(value: &str) -> bool {
+    if value == "infinity" {
+        return true;
+    }
+    let number = value
+        .strip_suffix('%')
+        .or_else(|| value.strip_suffix(['K', 'M', 'G', 'T']))
+        .unwrap_or(value);
+    !number.is_empty() && number.parse::<f64>().is_ok_and(|n| n >= 0.0)
+}
+
+/// The next `count` times a calendar expression elapses, as printed by
+/// `systemd-analyze calendar` (e.g. `Mon 2026-10-19 09:00:00 CEST`).
+pub fn calendar_next_elapses(expr: &str, count: usize) -> Result<Vec<String>> {
+    let iterations = format!("--iterations={}", count.max(1));
+    let out = systemd_analyze(&["calendar", &iterations, expr])
+        .map_err(|err| anyhow::anyhow!("Invalid calendar expression '{expr}': {err}"))?;
+    Ok(parse_calendar_elapses(&out))
+}
+
+fn parse_calendar_elapses(out: &str) -> Vec<String> {
+    out.lines()
+        .filter_map(|line| line.trim().split_once(": "))
+        // Older systemd prints "Iteration #2", newer "Iter. #2".
+        .filter(|(key, _)| *key == "Next elapse" || key.starts_with("Iter"))
+        .map(|(_, value)| value.trim().to_string())
+        .collect()
+}
+
+fn systemd_analyze(args: &[&str]) -> Result<String> {
+    let output = Command::new("systemd-analyze")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run systemd-analyze {}", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub const UNIT_PREFIX: &str = "rclonesync-helper@";
+
+/// Job a unit of ours belongs to, e.g. `my docs` for `rclonesync-helper@my\x20docs.timer`.
+pub fn job_from_unit(unit: &str) -> Option<String> {
+    instance_of(unit).map(unescape_instance)
+}
+
+/// The (still escaped) instance of one of our units; `None` for the templates themselves.
+fn instance_of(unit: &str) -> Option<&str> {
+    let rest = unit.strip_prefix(UNIT_PREFIX)?;
+    rest.strip_suffix(".timer")
+        .or_else(|| rest.strip_suffix(".service"))
+        .filter(|instance| !instance.is_empty())
+}
+
+/// Escape a job name for use as a unit instance, like `systemd-escape`: `/` becomes `-`, and
+/// `-`, a leading `.` and anything but ASCII letters, digits, `:`, `_` and `.` become `\xNN`.
+pub fn escape_instance(job: &str) -> String {
+    let mut out = String::with_capacity(job.len());
+    for (i, b) in job.bytes().enumerate() {
+        match b {
+            b'/' => out.push('-'),
+            b'.' if i == 0 => out.push_str("\\x2e"),
+            b if b.is_ascii_alphanumeric() || matches!(b, b':' | b'_' | b'.') => {
+                out.push(b as char)
+            }
+            b => out.push_str(&format!("\\x{b:02x}")),
+        }
+    }
+    out
+}
+
+/// Reverse `escape_instance` (what `%I` expands to).
+fn unescape_instance(instance: &str) -> String {
+    let mut bytes = Vec::with_capacity(instance.len());
+    let mut rest = instance.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        let hex = tail
+            .strip_prefix(b"x")
+            .and_then(|t| t.get(..2))
+            .and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+        match (b, hex) {
+            (b'\\', Some(byte)) => {
+                bytes.push(byte);
+                rest = &tail[3..];
+                continue;
+            }
+            (b'-', _) => bytes.push(b'/'),
+            (b, _) => bytes.push(b),
+        }
+        rest = tail;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn template_unit_name(kind: &str) -> String {
+    format!("{UNIT_PREFIX}.{kind}")
+}
+
+/// The drop-in holding a job's `[Timer]` triggers, relative to the unit dir.
+fn schedule_dropin(job: &str) -> String {
+    format!("{}.d/schedule.conf", timer_unit_name(job))
+}
+
+/// The drop-in holding a job's resource limits, relative to the unit dir.
+fn resources_dropin(job: &str) -> String {
+    format!("{}.d/resources.conf", service_unit_name(job))
+}
+
 fn timer_unit_name(job: &str) -> String {
-    format!("rclonesync-helper@{job}.timer")
+    format!("{UNIT_PREFIX}{}.timer", escape_instance(job))
 }
 
 fn service_unit_name(job: &str) -> String {
-    format!("rclonesync-helper@{job}.service")
+    format!("{UNIT_PREFIX}{}.service", escape_instance(job))
+}
+
+/// The last `lines` messages a unit logged (`journalctl --user -u <unit>`), oldest first.
+fn journal_tail(unit: &str, lines: usize) -> Result<Vec<String>> {
+    let output = Command::new("journalctl")
+        .args(["--user", "-u", unit, "--no-pager", "-o", "cat", "-n"])
+        .arg(lines.to_string())
+        .output()
+        .context("Failed to run journalctl")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "journalctl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
 }
 
 fn systemctl_user(args: &[&str]) -> Result<String> {
@@ -137,95 +702,115 @@ fn systemctl_user(args: &[&str]) -> Result<String> {
     Ok(stdout)
 }
 
-fn is_enabled(unit: &str) -> Result<bool> {
-    let output = Command::new("systemctl")
-        .arg("--user")
-        .args(["is-enabled", unit])
-        .output()?;
-    Ok(output.status.success())
+fn systemd_user_dir() -> Result<PathBuf> {
+    if let Some(xdg_config) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Ok(PathBuf::from(xdg_config).join("systemd/user"));
+    }
+    let home = std::env::var_os("HOME").context("HOME is not set")?;
+    Ok(PathBuf::from(home).join(".config/systemd/user"))
 }
 
-fn is_active(unit: &str) -> Result<bool> {
-    let output = Command::new("systemctl")
-        .arg("--user")
-        .args(["is-active", unit])
-        .output()?;
-    Ok(output.status.success())
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn systemctl_show_property(unit: &str, prop: &str) -> Result<Option<String>> {
-    let out = systemctl_user(&["show", unit, "-p", prop])?;
-    // Format: Key=value
-    let value = out
-        .lines()
-        .find_map(|line| line.strip_prefix(&format!("{prop}=")))
-        .map(|s| s.to_string());
-    Ok(value)
-}
-
-fn systemctl_list_timer_next(unit: &str) -> Result<Option<String>> {
-    // Output columns are separated by multiple spaces; we parse the first "NEXT" column.
-    // Example:
-    // Tue 2026-01-06 14:05:00 CET  55min left  Tue 2026-01-06 13:10:00 CET  ...  <unit>
-    let out = systemctl_user(&["list-timers", "--all", "--no-pager", "--no-legend", unit])?;
-    let line = out.lines().next().unwrap_or("").trim();
-    if line.is_empty() {
-        return Ok(None);
-    }
-    let tokens: Vec<&str> = line.split_whitespace().collect();
-    if tokens.is_empty() {
-        return Ok(None);
-    }
-    if tokens[0].eq_ignore_ascii_case("n/a") || tokens[0] == "-" {
-        return Ok(None);
-    }
-    if tokens.len() < 3 {
-        return Ok(None);
-    }
-
-    let mut next = vec![tokens[0], tokens[1], tokens[2]];
-    // Include timezone if it looks like one (simple heuristic).
-    if tokens.len() >= 4 {
-        let tz = tokens[3];
-        let tz_like = tz.len() <= 6
-            && tz
-                .chars()
-                .all(|c| c.is_ascii_alphabetic() || c == '_' || c == '/');
-        if tz_like {
-            next.push(tz);
-        }
-    }
-    Ok(Some(next.join(" ")))
-}
-
-fn parse_next_elapse(value: Option<String>) -> Option<String> {
-    let s = value?;
-    let s = s.trim();
-    if s.is_empty() || s.eq_ignore_ascii_case("n/a") || s == "0" {
-        return None;
-    }
-
-    // Depending on systemd/version, this can be:
-    // - a human readable timestamp (e.g. "Mon 2026-01-05 15:00:00 UTC")
-    // - a raw usec timestamp (e.g. "1736089200000000")
-    if s.chars().all(|c| c.is_ascii_digit()) {
-        if let Ok(us) = s.parse::<u64>() {
-            let secs = (us / 1_000_000) as i64;
-            let nsec = ((us % 1_000_000) * 1_000) as u32;
-            if let Some(ts) = DateTime::<Utc>::from_timestamp(secs, nsec) {
-                let local = ts.with_timezone(&Local);
-                return Some(local.format("%F %T").to_string());
-            }
-        }
+    #[test]
+    fn renders_timer_triggers_from_the_schedule() {
+        assert_eq!(
+            timer_triggers(&Schedule::default()),
+            "OnCalendar=hourly\nPersistent=true\n"
+        );
+        let schedule = Schedule {
+            interval: Some(" 30min ".into()),
+            randomized_delay: Some("5min".into()),
+            on_boot: Some(String::new()),
+            on_startup: Some("2min".into()),
+            ..Schedule::default()
+        };
+        assert_eq!(
+            timer_triggers(&schedule),
+            "OnActiveSec=30min\nOnUnitActiveSec=30min\nRandomizedDelaySec=5min\nOnStartupSec=2min\n"
+        );
+        assert_eq!(schedule.describe(), "every 30min");
+
+        let out = "  Original form: Mon..Fri 09:00
+Normalized form: Mon..Fri *-*-* 09:00:00
+    Next elapse: Mon 2026-10-19 09:00:00 CEST
+       (in UTC): Mon 2026-10-19 07:00:00 UTC
+       From now: 2 days left
+       Iter. #2: Tue 2026-10-20 09:00:00 CEST
+       From now: 3 days left
+";
+        assert_eq!(
+            parse_calendar_elapses(out),
+            vec![
+                "Mon 2026-10-19 09:00:00 CEST",
+                "Tue 2026-10-20 09:00:00 CEST"
+            ]
+        );
     }
 
-    Some(s.to_string())
-}
+    #[test]
+    fn renders_resource_limits_into_the_service_dropin() {
+        assert_eq!(
+            render_resources(&Resources::default()),
+            "[Unit]\nWants=network-online.target\nAfter=network-online.target\n\n[Service]\nNice=19\nIOSchedulingClass=idle\nCPUWeight=20\nIOWeight=20\n"
+        );
+        let resources = Resources {
+            nice: None,
+            io_scheduling_class: None,
+            memory_max: Some("1.5G".into()),
+            timeout: Some("2h".into()),
+            wait_for_network: false,
+            require_ac_power: true,
+            ..Resources::default()
+        };
+        assert_eq!(
+            render_resources(&resources),
+            "[Unit]\nConditionACPower=true\n\n[Service]\nCPUWeight=20\nIOWeight=20\nMemoryMax=1.5G\nTimeoutStartSec=2h\n"
+        );
+        assert!(valid_memory_size("20%") && valid_memory_size("infinity"));
+        assert!(!valid_memory_size("lots") && !valid_memory_size("G"));
+    }
 
-fn systemd_user_dir() -> Result<PathBuf> {
-    if let Some(xdg_config) = std::env::var_os("XDG_CONFIG_HOME") {
-        return Ok(PathBuf::from(xdg_config).join("systemd/user"));
+    #[test]
+    fn escapes_job_names_like_systemd_escape() {
+        assert_eq!(escape_instance("docs"), "docs");
+        assert_eq!(escape_instance("my docs-2.0"), "my\\x20docs\\x2d2.0");
+        assert_eq!(escape_instance(".hidden"), "\\x2ehidden");
+        assert_eq!(
+            timer_unit_name("photos-nas"),
+            "rclonesync-helper@photos\\x2dnas.timer"
+        );
+        for job in ["docs", "my docs-2.0", ".hidden", "zażółć"] {
+            assert_eq!(job_from_unit(&service_unit_name(job)).as_deref(), Some(job));
+        }
+        assert_eq!(job_from_unit("rclonesync-helper@.service"), None);
+    }
+
+    #[test]
+    fn describes_the_last_service_result() {
+        let mut st = TimerStatus {
+            unit: timer_unit_name("docs"),
+            installed: true,
+            enabled: true,
+            active: true,
+            next_elapse: None,
+            last_trigger: None,
+            service_result: None,
+            exit_status: None,
+            journal_tail: vec![],
+            outdated: vec![],
+            manager_error: None,
+        };
+        assert_eq!(st.last_result(), None);
+        st.service_result = Some("success".into());
+        assert_eq!(st.last_result().as_deref(), Some("Succeeded"));
+        st.service_result = Some("exit-code".into());
+        st.exit_status = Some(2);
+        assert_eq!(
+            st.last_result().as_deref(),
+            Some("Failed: exit-code (status 2)")
+        );
     }
-    let home = std::env::var_os("HOME").context("HOME is not set")?;
-    Ok(PathBuf::from(home).join(".config/systemd/user"))
 }
@@ -0,0 +1,235 @@
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use futures_util::{Stream, StreamExt};
+use zbus::blocking::Connection;
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::OwnedObjectPath;
+
+/// The user's systemd instance (`systemctl --user`) on the session bus.
+#[zbus::proxy(
+    interface = "org.freedesktop.systemd1.Manager",
+    default_service = "org.freedesktop.systemd1",
+    default_path = "/org/freedesktop/systemd1"
+)]
+trait Manager {
+    fn load_unit(&self, name: &str) -> zbus::Result<OwnedObjectPath>;
+
+    /// Ask for PropertiesChanged signals on units; systemd only sends them to subscribers.
+    fn subscribe(&self) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn version(&self) -> zbus::Result<String>;
+}
+
+#[zbus::proxy(
+    interface = "org.freedesktop.systemd1.Unit",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Unit {
+    #[zbus(property)]
+    fn active_state(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn unit_file_state(&self) -> zbus::Result<String>;
+}
+
+#[zbus::proxy(
+    interface = "org.freedesktop.systemd1.Timer",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Timer {
+    #[zbus(property, name = "NextElapseUSecRealtime")]
+    fn next_elapse_realtime(&self) -> zbus::Result<u64>;
+
+    #[zbus(property, name = "NextElapseUSecMonotonic")]
+    fn next_elapse_monotonic(&self) -> zbus::Result<u64>;
+
+    #[zbus(property, name = "LastTriggerUSec")]
+    fn last_trigger(&self) -> zbus::Result<u64>;
+}
+
+#[zbus::proxy(
+    interface = "org.freedesktop.systemd1.Service",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Service {
+    #[zbus(property)]
+    fn result(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn exec_main_status(&self) -> zbus::Result<i32>;
+
+    #[zbus(property)]
+    fn exec_main_start_timestamp(&self) -> zbus::Result<u64>;
+}
+
+/// What systemd reports about a job's timer and the service it triggers.
+#[derive(Debug, Clone, Default)]
+pub struct UnitProperties {
+    pub enabled: bool,
+    pub active: bool,
+    /// Next time the timer elapses, formatted for display.
+    pub next_elapse: Option<String>,
+    pub last_trigger: Option<String>,
+    /// The service's `Result` of its last run (`success`, `exit-code`, `timeout`, ...).
+    pub service_result: Option<String>,
+    /// Exit status of the service's last run.
+    pub exit_status: Option<i32>,
+}
+
+/// Session bus connection, opened on first use and shared afterwards.
+fn connection() -> Result<Connection> {
+    static CONNECTION: OnceLock<Connection> = OnceLock::new();
+    if let Some(conn) = CONNECTION.get() {
+        return Ok(conn.clone());
+    }
+    let conn = Connection::session().context("Failed to connect to the session D-Bus")?;
+    Ok(CONNECTION.get_or_init(|| conn).clone())
+}
+
+/// Check that the user manager answers on D-Bus.
+pub fn ping() -> Result<()> {
+    let conn = connection()?;
+    ManagerProxyBlocking::new(&conn)?
+        .version()
+        .context("systemd --user is not running on the session bus")?;
+    Ok(())
+}
+
+/// The user manager's major version, e.g. `255` for `255.4-1ubuntu8`.
+pub fn manager_version() -> Result<u32> {
+    let conn = connection()?;
+    let version = ManagerProxyBlocking::new(&conn)?.version()?;
+    let major: String = version.chars().take_while(char::is_ascii_digit).collect();
+    major
+        .parse()
+        .with_context(|| format!("Unrecognised systemd version '{version}'"))
+}
+
+/// Query the timer and service units in one go over D-Bus.
+pub fn unit_properties(timer: &str, service: &str) -> Result<UnitProperties> {
+    let conn = connection()?;
+    let manager = ManagerProxyBlocking::new(&conn)?;
+    let timer_path = manager
+        .load_unit(timer)
+        .with_context(|| format!("Failed to load {timer}"))?;
+    let service_path = manager
+        .load_unit(service)
+        .with_context(|| format!("Failed to load {service}"))?;
+
+    let unit = UnitProxyBlocking::builder(&conn)
+        .path(&timer_path)?
+        .cache_properties(CacheProperties::No)
+        .build()?;
+    let timer_props = TimerProxyBlocking::builder(&conn)
+        .path(&timer_path)?
+        .cache_properties(CacheProperties::No)
+        .build()?;
+    let service_props = ServiceProxyBlocking::builder(&conn)
+        .path(&service_path)?
+        .cache_properties(CacheProperties::No)
+        .build()?;
+
+    let next_elapse = match timer_props.next_elapse_realtime()? {
+        0 => monotonic_to_realtime(timer_props.next_elapse_monotonic()?),
+        usec => format_usec(usec),
+    };
+    // `Result` stays `success` for a service that never ran; only report it after a run.
+    let ran = service_props.exec_main_start_timestamp()? != 0;
+    Ok(UnitProperties {
+        enabled: matches!(
+            unit.unit_file_state()?.as_str(),
+            "enabled" | "enabled-runtime"
+        ),
+        active: unit.active_state()? == "active",
+        next_elapse,
+        last_trigger: format_usec(timer_props.last_trigger()?),
+        service_result: ran.then(|| service_props.result()).transpose()?,
+        exit_status: ran.then(|| service_props.exec_main_status()).transpose()?,
+    })
+}
+
+/// Names of this app's units whose properties changed (a timer firing, a run starting or
+/// finishing, a unit being enabled...). Ends if the bus goes away.
+pub async fn watch_unit_changes() -> Result<impl Stream<Item = String>> {
+    let conn = zbus::Connection::session()
+        .await
+        .context("Failed to connect to the session D-Bus")?;
+    ManagerProxy::new(&conn).await?.subscribe().await?;
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender("org.freedesktop.systemd1")?
+        .interface("org.freedesktop.DBus.Properties")?
+        .member("PropertiesChanged")?
+        .path_namespace("/org/freedesktop/systemd1/unit")?
+        .build();
+    let stream = zbus::MessageStream::for_match_rule(rule, &conn, None).await?;
+    Ok(stream.filter_map(|msg| async move {
+        let msg = msg.ok()?;
+        let header = msg.header();
+        unit_name_from_path(header.path()?.as_str())
+            .filter(|name| name.starts_with(crate::systemd::UNIT_PREFIX))
+    }))
+}
+
+/// Undo systemd's object path escaping: `/org/freedesktop/systemd1/unit/foo_2dbar_2eservice`
+/// is `foo-bar.service`.
+fn unit_name_from_path(path: &str) -> Option<String> {
+    let escaped = path.strip_prefix("/org/freedesktop/systemd1/unit/")?;
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut rest = escaped.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'_' && tail.len() >= 2 {
+            let hex = std::str::from_utf8(&tail[..2]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn format_usec(usec: u64) -> Option<String> {
+    if usec == 0 || usec == u64::MAX {
+        return None;
+    }
+    let ts = DateTime::<Utc>::from_timestamp_micros(i64::try_from(usec).ok()?)?;
+    Some(ts.with_timezone(&Local).format("%a %F %T").to_string())
+}
+
+/// Interval timers only have a monotonic next elapse. Convert it with the current uptime, which
+/// may be off by the time spent suspended.
+fn monotonic_to_realtime(usec: u64) -> Option<String> {
+    if usec == 0 || usec == u64::MAX {
+        return None;
+    }
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    let uptime_secs: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    let now_usec = Utc::now().timestamp_micros();
+    let delta = usec as i64 - (uptime_secs * 1_000_000.0) as i64;
+    format_usec(u64::try_from(now_usec + delta).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_unit_object_paths() {
+        assert_eq!(
+            unit_name_from_path(
+                "/org/freedesktop/systemd1/unit/rclonesync_2dhelper_40my_5fdocs_2etimer"
+            )
+            .as_deref(),
+            Some("rclonesync-helper@my_docs.timer")
+        );
+        assert_eq!(
+            unit_name_from_path("/org/freedesktop/systemd1/job/42"),
+            None
+        );
+    }
+}